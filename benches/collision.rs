@@ -0,0 +1,129 @@
+//! Benchmarks the projectile-enemy collision query used when updating projectiles against checking every pair,
+//! in a late game scenario of many shotgun clones against a horde of slimes.
+//!
+//! Run with `cargo bench --bench collision`
+
+use std::{hint::black_box, time::Instant};
+
+use macroquad::{math::Vec2, rand};
+
+#[allow(dead_code)]
+#[path = "../src/spatial.rs"]
+mod spatial;
+use spatial::*;
+
+const SCREEN_WIDTH: f32 = 192.0;
+const SCREEN_HEIGHT: f32 = 144.0;
+const CELL_SIZE: f32 = 16.0;
+const ENEMIES: usize = 40;
+/// Triple shotgun with 2 clones each, on 4 towers, with projectiles alive for about 10 shots
+const PROJECTILES: usize = 3 * 3 * 4 * 10;
+const TICKS: usize = 2000;
+const RADIUS: f32 = 8.0;
+
+struct Scenario {
+    enemies: Vec<Vec2>,
+    /// Start and end of each projectile's movement this tick
+    projectiles: Vec<(Vec2, Vec2)>,
+}
+impl Scenario {
+    fn new() -> Self {
+        rand::srand(0);
+        // enemies bunched up along a horizontal path, like a late round
+        let enemies = (0..ENEMIES)
+            .map(|_| {
+                Vec2::new(
+                    rand::gen_range(0.0, SCREEN_WIDTH),
+                    rand::gen_range(60.0, 84.0),
+                )
+            })
+            .collect();
+        let projectiles = (0..PROJECTILES)
+            .map(|_| {
+                let from = Vec2::new(
+                    rand::gen_range(0.0, SCREEN_WIDTH),
+                    rand::gen_range(0.0, SCREEN_HEIGHT),
+                );
+                let direction = Vec2::from_angle(rand::gen_range(0.0, std::f32::consts::TAU));
+                (from, from + direction * rand::gen_range(2.0, 8.0))
+            })
+            .collect();
+        Self {
+            enemies,
+            projectiles,
+        }
+    }
+}
+
+fn brute_force(scenario: &Scenario) -> usize {
+    let mut hits = 0;
+    for (from, to) in &scenario.projectiles {
+        for enemy in &scenario.enemies {
+            if segment_circle_entry(*from, *to, *enemy, RADIUS).is_some() {
+                hits += 1;
+            }
+        }
+    }
+    hits
+}
+
+/// Uses the same query as projectile updates, including reusing the buffers between projectiles
+fn grid(
+    scenario: &Scenario,
+    grid: &mut SpatialGrid,
+    candidates: &mut Vec<usize>,
+    hits: &mut Vec<(f32, usize)>,
+) -> usize {
+    grid.clear();
+    for (index, enemy) in scenario.enemies.iter().enumerate() {
+        grid.insert(index, *enemy);
+    }
+    let mut total = 0;
+    for (from, to) in &scenario.projectiles {
+        grid.query_segment(
+            *from,
+            *to,
+            RADIUS,
+            |index| Some(scenario.enemies[index]),
+            candidates,
+            hits,
+        );
+        total += hits.len();
+    }
+    total
+}
+
+fn main() {
+    let scenario = Scenario::new();
+    let mut spatial_grid = SpatialGrid::new(SCREEN_WIDTH, SCREEN_HEIGHT, CELL_SIZE);
+    let mut candidates = Vec::new();
+    let mut hits = Vec::new();
+    assert_eq!(
+        brute_force(&scenario),
+        grid(&scenario, &mut spatial_grid, &mut candidates, &mut hits),
+        "broad phase missed hits!"
+    );
+
+    let start = Instant::now();
+    for _ in 0..TICKS {
+        black_box(brute_force(black_box(&scenario)));
+    }
+    let brute_force_time = start.elapsed();
+
+    let start = Instant::now();
+    for _ in 0..TICKS {
+        black_box(grid(
+            black_box(&scenario),
+            &mut spatial_grid,
+            &mut candidates,
+            &mut hits,
+        ));
+    }
+    let grid_time = start.elapsed();
+
+    println!(
+        "{ENEMIES} enemies, {PROJECTILES} projectiles, {TICKS} ticks\nbrute force: {:?}/tick\ngrid: {:?}/tick",
+        brute_force_time / TICKS as u32,
+        grid_time / TICKS as u32,
+    );
+}
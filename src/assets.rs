@@ -10,8 +10,8 @@ use macroquad::{
 
 use crate::{
     map::{
-        parse_points_from_tilemap, parse_spawnpoints_from_tilemap, parse_tilemap_layer, Map,
-        Spritesheet,
+        parse_map_property, parse_points_from_tilemap, parse_spawnpoints_from_tilemap,
+        parse_tilemap_layer, Map, Spritesheet,
     },
     rounds::{decode_rounds, Round, RoundManager},
 };
@@ -42,6 +42,7 @@ pub enum ProjectileSound {
     Hit,
     None,
     Explosion,
+    Crit,
 }
 impl ProjectileSound {
     pub fn play(&self, sfx_manager: &SFXManager) {
@@ -49,6 +50,7 @@ impl ProjectileSound {
             ProjectileSound::None => {}
             ProjectileSound::Hit => SFXManager::play_sound(&sfx_manager.hit),
             ProjectileSound::Explosion => SFXManager::play_sound(&sfx_manager.explosion),
+            ProjectileSound::Crit => SFXManager::play_sound(&sfx_manager.crit),
         }
     }
 }
@@ -61,12 +63,15 @@ type Sfx = (Vec<Sound>, f32);
 pub struct SFXManager {
     pub explosion: Sfx,
     pub hit: Sfx,
+    pub crit: Sfx,
 }
 impl SFXManager {
     pub async fn new() -> Self {
         SFXManager {
             explosion: (load_sounds("data/sfx/explosion/").await, 0.3),
             hit: (load_sounds("data/sfx/hit/").await, 0.2),
+            // crits reuse the explosion sounds, quieter, to stand out from regular hits
+            crit: (load_sounds("data/sfx/explosion/").await, 0.15),
         }
     }
     pub fn play_sound(sounds: &Sfx) {
@@ -145,6 +150,9 @@ pub fn load_maps() -> Vec<Map> {
         let obstructions = parse_tilemap_layer(&data, "Obstructions").expect("bad map data");
         let out_of_bounds = parse_tilemap_layer(&data, "Out of Bounds").expect("bad map data");
         let path = parse_tilemap_layer(&data, "Path").expect("bad map data");
+        let gold_multiplier = parse_map_property(&data, "gold_multiplier")
+            .map(|f| f.parse().expect("bad map data"))
+            .unwrap_or(1.0);
 
         let map = Map {
             name,
@@ -153,6 +161,7 @@ pub fn load_maps() -> Vec<Map> {
             out_of_bounds,
             obstructions,
             tower_spawnpoints: parse_spawnpoints_from_tilemap(&path),
+            gold_multiplier,
         };
         maps.push(map);
     }
@@ -187,6 +196,21 @@ pub fn load_round_data(seed: u64) -> RoundManager {
     }
 }
 
+pub fn load_enemy_data() -> String {
+    #[cfg(feature = "bundled")]
+    {
+        DATA.get_file("enemies.txt")
+            .unwrap()
+            .contents_utf8()
+            .unwrap()
+            .to_string()
+    }
+    #[cfg(not(feature = "bundled"))]
+    {
+        read_to_string("data/enemies.txt").expect("data/enemies.txt is missing!!")
+    }
+}
+
 pub fn get_sublevels_hashmap() -> SublevelHashmap {
     let mut sublevels: SublevelHashmap = HashMap::new();
 
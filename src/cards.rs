@@ -22,6 +22,8 @@ pub fn get_cards() -> Vec<Card> {
         library::high_precision(),
         library::scatter(),
         library::ghost_shot(),
+        library::bouncy(),
+        library::keen_eye(),
         library::shock(),
         library::freezeify(),
         library::boomerangify(),
@@ -29,9 +31,20 @@ pub fn get_cards() -> Vec<Card> {
         library::greed(),
         library::pay2win(),
         library::confetti_trail(),
+        library::add_trigger(),
+        library::amplify(),
+        library::lucky_shot(),
+        library::deadeye(),
+        library::cheap_shot(),
+        library::brittle_shot(),
+        library::turbo(),
+        library::longevity(),
         // multidraw
         library::double(),
         library::triple(),
+        library::divide_by_2(),
+        library::divide_by_3(),
+        library::divide_by_4(),
         // projectiles
         library::rocket(),
         library::bomb(),
@@ -115,13 +128,15 @@ pub enum CardType {
     Modifier(CardModifierData),
     /// usize is how many cards to draw.
     Multidraw(usize),
+    /// Draws the next card and casts it usize amount of times.
+    Divide(usize),
 }
 impl CardType {
     fn get_border_color(&self) -> Color {
         match self {
             Self::Projectile(_, _) => Color::from_hex(0x9e2835),
             Self::Modifier(_) => Color::from_hex(0x4f6781),
-            Self::Multidraw(_) => Color::from_hex(0xafbfd2),
+            Self::Multidraw(_) | Self::Divide(_) => Color::from_hex(0xafbfd2),
         }
     }
 }
@@ -179,18 +194,46 @@ pub struct Projectile {
     /// therefore it has 2 clones.
     pub clones_amount: u8,
     pub only_enemy_triggers: bool,
+    /// Does the projectile lie on the ground, so flying enemies can't be hit by it
+    pub ground: bool,
     /// Is the projectile immune to being rotated, i.e. by homing modifier?
     pub straight: bool,
     pub hit_sound: ProjectileSound,
     pub fire_sound: ProjectileSound,
     pub random_damage: Option<(u8, u8)>,
+    /// Ids of enemies hit, and the projectile's life at the time of the hit
+    pub hit_enemies: Vec<(u32, f32)>,
+    /// Has a reaction already made this projectile, or one that released it, release an extra payload.
+    /// Only one is allowed per cast, so ex. lightning chaining through acid doesn't multiply every hop
+    pub extra_payload_released: bool,
     pub modifier_data: CardModifierData,
 }
 impl Projectile {
+    /// Multiplies the damage of the projectile, and recursively of the projectiles it releases
+    pub fn scale_damage(&mut self, factor: f32) {
+        for damage in self.modifier_data.damage.values_mut() {
+            *damage *= factor;
+        }
+        for child in self.payload.iter_mut().chain(self.death_payload.iter_mut()) {
+            if let CardType::Projectile(child, _) = &mut child.ty {
+                child.scale_damage(factor);
+            }
+        }
+    }
+    /// Does the projectile, or any projectile it releases, deal damage?
+    pub fn deals_damage(&self) -> bool {
+        !self.modifier_data.damage.is_empty()
+            || self.random_damage.is_some()
+            || self.payload.iter().chain(self.death_payload.iter()).any(
+                |child| matches!(&child.ty, CardType::Projectile(child, _) if child.deals_damage()),
+            )
+    }
     pub fn fire_payload(&self) -> Vec<Projectile> {
         let mut context = FiringContext::default();
         // children inherit parent's gold factor because i feel like the payload's kills should be attributed to the parent
         context.modifier_data.gold_factor = self.modifier_data.gold_factor;
+        // and its detection, so ex. a keen eyed rocket's explosion can hit the camouflaged enemies it hits
+        context.modifier_data.detection = self.modifier_data.detection;
         fire_deck(
             self.x - SPRITE_SIZE / 2.0,
             self.y - SPRITE_SIZE / 2.0,
@@ -198,6 +241,9 @@ impl Projectile {
             self.payload.clone(),
             &mut context,
         );
+        for child in context.spawn_list.iter_mut() {
+            child.extra_payload_released = self.extra_payload_released;
+        }
         context.spawn_list
     }
 }
@@ -233,13 +279,17 @@ pub struct CardModifierData {
     /// How many frames of poison does this give enemies?
     pub poison: u8,
     pub lifetime: f32,
-    /// Can projectile hit multiple enemies
-    pub piercing: bool,
+    /// How many more enemies the projectile can hit after the first. [INFINITE_PIERCE] never runs out
+    pub pierce: u8,
+    /// Frames before the projectile can hit the same enemy again. 0 means each enemy can only be hit once
+    pub rehit_frames: u8,
     /// Stops projectile from interacting with enemies at all, like the bomb,
     /// which doesn't get destroyed, nor deal damage on impact.
     pub anti_piercing: bool,
     /// Can projectile travel through walls/obstacles
     pub ghost: bool,
+    /// How many times the projectile bounces off walls/obstacles before dying
+    pub bounces: u8,
     /// Does projectile arc back towards caster
     pub boomerang: bool,
     /// Does the projectile follow a slithering path
@@ -251,6 +301,28 @@ pub struct CardModifierData {
     pub spread: f32,
     /// Factor for how much gold is earned per enemy kill
     pub gold_factor: Option<f32>,
+    /// Factor damage is multiplied by, after all additive stats are summed
+    pub damage_factor: Option<f32>,
+    /// Factor speed is multiplied by, after all additive stats are summed
+    pub speed_factor: Option<f32>,
+    /// Factor lifetime is multiplied by, after all additive stats are summed
+    pub lifetime_factor: Option<f32>,
+    /// Factor shoot delay and reload time are multiplied by, after all additive stats are summed
+    pub delay_factor: Option<f32>,
+    /// Makes the next projectile drawn act as a trigger
+    pub add_trigger: bool,
+    /// Chance (0.0 - 1.0) of a hit being critical
+    pub crit_chance: f32,
+    /// Added onto [CRIT_MULTIPLIER] for critical hits
+    pub crit_multiplier: f32,
+    /// Hits on stunned enemies are always critical
+    pub crit_stunned: bool,
+    /// Hits on frozen enemies are always critical
+    pub crit_frozen: bool,
+    /// Distance (in pixels) enemies hit are pushed back along the path. Divided by enemy size
+    pub knockback: f32,
+    /// Can projectile target and hit camouflaged enemies
+    pub detection: bool,
     pub damage: HashMap<DamageType, f32>,
 }
 impl CardModifierData {
@@ -266,6 +338,21 @@ impl CardModifierData {
             };
             fields.push((k, v.to_string()));
         }
+        for (k, factor) in [
+            ("damage", self.damage_factor),
+            ("speed", self.speed_factor),
+            ("lifetime", self.lifetime_factor),
+            ("delays", self.delay_factor),
+        ] {
+            if fields.len() >= 3 {
+                break;
+            }
+            if let Some(factor) = factor {
+                let percent = ((factor - 1.0) * 100.0).round();
+                let sign = if percent >= 0.0 { "+" } else { "" };
+                fields.push((k, format!("{sign}{percent}%")));
+            }
+        }
         for (k, field) in [
             ("shoot delay", self.shoot_delay),
             ("reload time", self.recharge_speed),
@@ -281,6 +368,12 @@ impl CardModifierData {
         if fields.len() < 3 && self.spread != 0.0 {
             fields.push(("spread", self.spread.to_degrees().to_string() + " deg"));
         }
+        if fields.len() < 3 && self.crit_chance != 0.0 {
+            fields.push((
+                "crit chance",
+                ((self.crit_chance * 100.0).round()).to_string() + "%",
+            ));
+        }
 
         fields
     }
@@ -290,6 +383,29 @@ impl CardModifierData {
         self.shoot_delay += other.shoot_delay;
         self.recharge_speed += other.recharge_speed;
     }
+    /// Multiplies the additive stats by the factor fields, and then clears them so they aren't applied twice.
+    /// Should be called once all cards affecting the stats have been merged, such that additive stats always
+    /// get summed before factors are applied.
+    pub fn apply_factors(&mut self) {
+        if let Some(factor) = self.damage_factor.take() {
+            for amount in self.damage.values_mut() {
+                *amount *= factor;
+            }
+        }
+        if let Some(factor) = self.speed_factor.take() {
+            self.speed *= factor;
+        }
+        if let Some(factor) = self.lifetime_factor.take() {
+            // negative lifetimes mark projectiles that never despawn, like road thorns
+            if self.lifetime >= 0.0 {
+                self.lifetime *= factor;
+            }
+        }
+        if let Some(factor) = self.delay_factor.take() {
+            self.shoot_delay *= factor;
+            self.recharge_speed *= factor;
+        }
+    }
     pub fn merge(&mut self, other: &CardModifierData) {
         self.shoot_delay += other.shoot_delay;
         self.recharge_speed += other.recharge_speed;
@@ -297,8 +413,10 @@ impl CardModifierData {
         self.smart_aim |= other.smart_aim;
         self.homing |= other.homing;
         self.lifetime += other.lifetime;
-        self.piercing |= other.piercing;
+        self.pierce = self.pierce.saturating_add(other.pierce);
+        self.rehit_frames = self.rehit_frames.max(other.rehit_frames);
         self.ghost |= other.ghost;
+        self.bounces = self.bounces.saturating_add(other.bounces);
         self.boomerang |= other.boomerang;
         self.confetti_trail |= other.confetti_trail;
         self.snake |= other.snake;
@@ -306,10 +424,18 @@ impl CardModifierData {
         self.spread += other.spread;
         self.stuns += other.stuns;
         self.poison += other.poison;
-        if self.gold_factor.is_some() || other.gold_factor.is_some() {
-            self.gold_factor =
-                Some(self.gold_factor.unwrap_or(1.0) * other.gold_factor.unwrap_or(1.0));
-        }
+        self.add_trigger |= other.add_trigger;
+        self.crit_chance += other.crit_chance;
+        self.crit_multiplier += other.crit_multiplier;
+        self.crit_stunned |= other.crit_stunned;
+        self.crit_frozen |= other.crit_frozen;
+        self.knockback += other.knockback;
+        self.detection |= other.detection;
+        self.gold_factor = merge_factors(self.gold_factor, other.gold_factor);
+        self.damage_factor = merge_factors(self.damage_factor, other.damage_factor);
+        self.speed_factor = merge_factors(self.speed_factor, other.speed_factor);
+        self.lifetime_factor = merge_factors(self.lifetime_factor, other.lifetime_factor);
+        self.delay_factor = merge_factors(self.delay_factor, other.delay_factor);
         for (k, v) in &other.damage {
             if let Some(amt) = self.damage.get_mut(k) {
                 *amt += v;
@@ -320,6 +446,13 @@ impl CardModifierData {
     }
 }
 
+fn merge_factors(a: Option<f32>, b: Option<f32>) -> Option<f32> {
+    if a.is_none() && b.is_none() {
+        return None;
+    }
+    Some(a.unwrap_or(1.0) * b.unwrap_or(1.0))
+}
+
 #[derive(Clone, Default)]
 /// A card used by towers
 pub struct Card {
@@ -330,6 +463,8 @@ pub struct Card {
     pub sprite: usize,
     pub is_trigger: bool,
     pub show_child_stats: bool,
+    /// Upgrade level of card, gained through fusing two identical cards. 0 is a regular card
+    pub level: u8,
 }
 impl Debug for Card {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -342,6 +477,26 @@ impl PartialEq for Card {
     }
 }
 impl Card {
+    /// Can this card be fused with another, to create an upgraded version?
+    pub fn can_fuse(&self, other: &Card) -> bool {
+        self == other
+            && self.is_trigger == other.is_trigger
+            && self.level == other.level
+            && self.level < MAX_CARD_LEVEL
+            && self.has_upgrade()
+    }
+    /// Would upgrading the card change anything? Upgrades only raise damage and lower positive shoot delays,
+    /// so cards with neither would just be eaten by fusing
+    fn has_upgrade(&self) -> bool {
+        let (modifier_data, deals_damage) = match &self.ty {
+            CardType::Projectile(projectile, _) => {
+                (&projectile.modifier_data, projectile.deals_damage())
+            }
+            CardType::Modifier(modifier_data) => (modifier_data, !modifier_data.damage.is_empty()),
+            _ => return false,
+        };
+        deals_damage || modifier_data.shoot_delay > 0.0
+    }
     pub fn draw(&self, card_sheet: &Spritesheet, x: f32, y: f32) {
         draw_rectangle(
             x - 2.0,
@@ -358,8 +513,36 @@ impl Card {
             COLOR_BEIGE,
         );
         card_sheet.draw_tile(x, y, self.sprite, false, 0.0);
-        if self.is_trigger {
+        let adds_trigger = matches!(&self.ty, CardType::Modifier(data) if data.add_trigger);
+        if self.is_trigger || adds_trigger {
             card_sheet.draw_tile(x - 1.0, y - 1.0, 32 * 3, false, 0.0);
         }
+        // draw a pip for each level in the top right corner
+        for i in 0..self.level {
+            draw_rectangle(
+                x + SPRITE_SIZE - 1.0 - i as f32 * 2.0,
+                y - 1.0,
+                1.0,
+                1.0,
+                COLOR_YELLOW,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_fuses_cards_that_upgrade() {
+        let fusable = [library::dart(), library::rocket(), library::add_trigger()];
+        for card in fusable {
+            assert!(card.can_fuse(&card.clone()), "{}", card.name);
+        }
+        let unfusable = [library::keen_eye(), library::aiming(), library::greed()];
+        for card in unfusable {
+            assert!(!card.can_fuse(&card.clone()), "{}", card.name);
+        }
     }
 }
@@ -7,6 +7,41 @@ pub fn as_trigger(mut card: Card) -> Card {
     card.tier += 1;
     card
 }
+/// Upgrades a card by one level, like when two identical cards are fused
+pub fn as_upgraded(mut card: Card) -> Card {
+    card.level += 1;
+    let modifier_data = match &mut card.ty {
+        CardType::Projectile(projectile, _) => {
+            // also upgrade damage of payloads, so ex. the rocket's explosion gets stronger
+            for child in projectile
+                .payload
+                .iter_mut()
+                .chain(projectile.death_payload.iter_mut())
+            {
+                if let CardType::Projectile(child, _) = &mut child.ty {
+                    child.scale_damage(UPGRADE_DAMAGE_FACTOR);
+                }
+            }
+            if let Some((min, max)) = projectile.random_damage {
+                projectile.random_damage = Some((min, (max as f32 * UPGRADE_DAMAGE_FACTOR) as u8));
+            }
+            &mut projectile.modifier_data
+        }
+        CardType::Modifier(modifier_data) => modifier_data,
+        _ => {
+            return card;
+        }
+    };
+    for damage in modifier_data.damage.values_mut() {
+        *damage *= UPGRADE_DAMAGE_FACTOR;
+    }
+    // cards that already speed the tower up don't get any faster
+    if modifier_data.shoot_delay > 0.0 {
+        modifier_data.shoot_delay =
+            (modifier_data.shoot_delay - UPGRADE_SHOOT_DELAY_REDUCTION).max(0.0);
+    }
+    card
+}
 pub fn confetti_trail() -> Card {
     Card {
         name: "confetti trail",
@@ -21,6 +56,116 @@ pub fn confetti_trail() -> Card {
         ..Default::default()
     }
 }
+pub fn add_trigger() -> Card {
+    Card {
+        name: "add trigger",
+        desc: "makes next proj\nrelease the card\nafter it on hit",
+        tier: 1,
+        ty: CardType::Modifier(CardModifierData {
+            shoot_delay: 0.1,
+            add_trigger: true,
+            ..Default::default()
+        }),
+        sprite: 1,
+        ..Default::default()
+    }
+}
+pub fn amplify() -> Card {
+    Card {
+        name: "amplify",
+        desc: "increases proj\ndamage by 40%",
+        tier: 2,
+        ty: CardType::Modifier(CardModifierData {
+            shoot_delay: 0.15,
+            damage_factor: Some(1.4),
+            ..Default::default()
+        }),
+        sprite: 1,
+        ..Default::default()
+    }
+}
+pub fn turbo() -> Card {
+    Card {
+        name: "turbo",
+        desc: "increases proj\nspeed by 50%",
+        tier: 1,
+        ty: CardType::Modifier(CardModifierData {
+            shoot_delay: 0.05,
+            speed_factor: Some(1.5),
+            ..Default::default()
+        }),
+        sprite: 1,
+        ..Default::default()
+    }
+}
+pub fn longevity() -> Card {
+    Card {
+        name: "longevity",
+        desc: "proj lasts 50%\nlonger",
+        tier: 1,
+        ty: CardType::Modifier(CardModifierData {
+            shoot_delay: 0.1,
+            lifetime_factor: Some(1.5),
+            ..Default::default()
+        }),
+        sprite: 1,
+        ..Default::default()
+    }
+}
+pub fn lucky_shot() -> Card {
+    Card {
+        name: "lucky shot",
+        desc: "gives projectile a\n20% chance to\ncrit",
+        tier: 1,
+        ty: CardType::Modifier(CardModifierData {
+            crit_chance: 0.2,
+            ..Default::default()
+        }),
+        sprite: 1,
+        ..Default::default()
+    }
+}
+pub fn deadeye() -> Card {
+    Card {
+        name: "deadeye",
+        desc: "crits deal much\nmore damage",
+        tier: 2,
+        ty: CardType::Modifier(CardModifierData {
+            shoot_delay: 0.1,
+            crit_chance: 0.05,
+            crit_multiplier: 1.5,
+            ..Default::default()
+        }),
+        sprite: 1,
+        ..Default::default()
+    }
+}
+pub fn cheap_shot() -> Card {
+    Card {
+        name: "cheap shot",
+        desc: "hits on stunned\nenemies always\ncrit",
+        tier: 1,
+        ty: CardType::Modifier(CardModifierData {
+            crit_stunned: true,
+            ..Default::default()
+        }),
+        sprite: 1,
+        ..Default::default()
+    }
+}
+pub fn brittle_shot() -> Card {
+    Card {
+        name: "brittle shot",
+        desc: "hits on frozen\nenemies always\ncrit",
+        tier: 1,
+        ty: CardType::Modifier(CardModifierData {
+            crit_frozen: true,
+            ..Default::default()
+        }),
+        sprite: 1,
+        ..Default::default()
+    }
+}
 pub fn star_cannon() -> Card {
     let explosion_projectile = Projectile {
         draw_type: ProjectileDrawType::Particle(particle::STAR_EXPLOSION),
@@ -29,7 +174,7 @@ pub fn star_cannon() -> Card {
         modifier_data: CardModifierData {
             speed: 0.0,
             lifetime: 0.0,
-            piercing: true,
+            pierce: INFINITE_PIERCE,
             damage: hashmap!(DamageType::Magic => 12.0),
             ..Default::default()
         },
@@ -143,9 +288,10 @@ pub fn yoyo() -> Card {
                 modifier_data: CardModifierData {
                     speed: 5.0,
                     lifetime: 45.0,
-                    piercing: true,
+                    pierce: INFINITE_PIERCE,
+                    rehit_frames: 15,
                     shoot_delay: 0.35,
-                    damage: hashmap!(DamageType::Pierce => 1.0),
+                    damage: hashmap!(DamageType::Pierce => 3.0),
                     boomerang: true,
                     ..Default::default()
                 },
@@ -285,6 +431,7 @@ pub fn hammer() -> Card {
             lifetime: 60.0,
             shoot_delay: 0.85,
             recharge_speed: -0.25,
+            knockback: 12.0,
             damage: hashmap!(DamageType::Pierce => 8.0),
             ..Default::default()
         },
@@ -300,6 +447,33 @@ pub fn hammer() -> Card {
         ..Default::default()
     }
 }
+pub fn bouncy() -> Card {
+    Card {
+        name: "bouncy",
+        desc: "makes proj bounce\noff walls twice",
+        ty: CardType::Modifier(CardModifierData {
+            shoot_delay: 0.05,
+            bounces: 2,
+            ..Default::default()
+        }),
+        sprite: 1,
+        tier: 1,
+        ..Default::default()
+    }
+}
+pub fn keen_eye() -> Card {
+    Card {
+        name: "keen eye",
+        desc: "lets proj target\nand hit camo\nenemies",
+        ty: CardType::Modifier(CardModifierData {
+            detection: true,
+            ..Default::default()
+        }),
+        sprite: 1,
+        tier: 1,
+        ..Default::default()
+    }
+}
 pub fn ghost_shot() -> Card {
     Card {
         name: "ghost shot",
@@ -373,7 +547,8 @@ pub fn sunbeam() -> Card {
             lifetime: 3.0,
             shoot_delay: -0.15,
             recharge_speed: -0.25,
-            piercing: true,
+            pierce: INFINITE_PIERCE,
+            rehit_frames: 1,
             damage: hashmap!(DamageType::Burn => 0.5),
             ..Default::default()
         },
@@ -398,7 +573,8 @@ pub fn death_ray() -> Card {
             lifetime: 3.0,
             shoot_delay: 0.85,
             recharge_speed: 0.65,
-            piercing: true,
+            pierce: INFINITE_PIERCE,
+            rehit_frames: 1,
             damage: hashmap!(DamageType::Magic => 25.0),
             ..Default::default()
         },
@@ -423,7 +599,8 @@ pub fn freeze_ray() -> Card {
             lifetime: 3.0,
             shoot_delay: 0.45,
             recharge_speed: 0.2,
-            piercing: true,
+            pierce: INFINITE_PIERCE,
+            rehit_frames: 1,
             damage: hashmap!(DamageType::Cold => 8.0),
             ..Default::default()
         },
@@ -445,8 +622,7 @@ pub fn supercharge() -> Card {
         desc: "makes tower faster",
         tier: 1,
         ty: CardType::Modifier(CardModifierData {
-            shoot_delay: -0.25,
-            recharge_speed: -0.11,
+            delay_factor: Some(0.7),
             ..Default::default()
         }),
         sprite: 25,
@@ -455,6 +631,7 @@ pub fn supercharge() -> Card {
 }
 pub fn road_thorns() -> Card {
     let projectile = Projectile {
+        ground: true,
         draw_type: ProjectileDrawType::Sprite(13, SpriteRotationMode::None),
         drag: 0.15,
         hit_sound: ProjectileSound::Hit,
@@ -537,7 +714,8 @@ pub fn bubble() -> Card {
         modifier_data: CardModifierData {
             speed: 0.0,
             lifetime: 10.0,
-            piercing: true,
+            pierce: INFINITE_PIERCE,
+            rehit_frames: 1,
             shoot_delay: 0.05,
             ..Default::default()
         },
@@ -575,6 +753,39 @@ pub fn triple() -> Card {
     }
 }
 
+pub fn divide_by_2() -> Card {
+    Card {
+        name: "divide by 2",
+        desc: "casts next card\ntwice, for less dmg",
+        tier: 1,
+        ty: CardType::Divide(2),
+        sprite: 5,
+        ..Default::default()
+    }
+}
+
+pub fn divide_by_3() -> Card {
+    Card {
+        name: "divide by 3",
+        desc: "casts next card\nthrice, for less dmg",
+        tier: 2,
+        ty: CardType::Divide(3),
+        sprite: 6,
+        ..Default::default()
+    }
+}
+
+pub fn divide_by_4() -> Card {
+    Card {
+        name: "divide by 4",
+        desc: "casts next card\nfour times, for\nless dmg",
+        tier: 2,
+        ty: CardType::Divide(4),
+        sprite: 45,
+        ..Default::default()
+    }
+}
+
 pub fn speed() -> Card {
     Card {
         name: "speedify",
@@ -734,6 +945,27 @@ pub fn razor() -> Card {
         ..Default::default()
     }
 }
+fn fire_patch() -> Card {
+    let projectile = Projectile {
+        ground: true,
+        draw_type: ProjectileDrawType::Particle(particle::FIRE_PATCH),
+        extra_size: SPRITE_SIZE / 2.0,
+        modifier_data: CardModifierData {
+            speed: 0.0,
+            lifetime: 210.0,
+            pierce: INFINITE_PIERCE,
+            rehit_frames: 1,
+            damage: hashmap!(DamageType::Burn => 0.05),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    Card {
+        ty: CardType::Projectile(projectile, false),
+        sprite: 1,
+        ..Default::default()
+    }
+}
 fn fire_explosion() -> Card {
     let explosion_projectile = Projectile {
         draw_type: ProjectileDrawType::Particle(particle::FIRE_EXPLOSION),
@@ -742,10 +974,12 @@ fn fire_explosion() -> Card {
         modifier_data: CardModifierData {
             speed: 0.0,
             lifetime: 0.0,
-            piercing: true,
+            pierce: INFINITE_PIERCE,
             damage: hashmap!(DamageType::Burn => 14.0),
             ..Default::default()
         },
+        // leave a patch of burning ground
+        death_payload: vec![fire_patch()],
         ..Default::default()
     };
     Card {
@@ -787,7 +1021,8 @@ pub fn explosion() -> Card {
             speed: 0.0,
             lifetime: 0.0,
             shoot_delay: 1.15,
-            piercing: true,
+            pierce: INFINITE_PIERCE,
+            knockback: 8.0,
             damage: hashmap!(DamageType::Burn => 13.0),
             ..Default::default()
         },
@@ -821,7 +1056,7 @@ pub fn stun_explosion() -> Card {
             lifetime: 0.0,
             shoot_delay: 0.85,
             recharge_speed: 0.15,
-            piercing: true,
+            pierce: INFINITE_PIERCE,
             damage: hashmap!(DamageType::Burn => 5.0),
             ..Default::default()
         },
@@ -914,11 +1149,11 @@ pub fn rocket() -> Card {
 pub fn piercing() -> Card {
     Card {
         name: "piercing",
-        desc: "proj pierces enemies",
+        desc: "proj pierces\n3 more enemies",
         tier: 3,
         sprite: 24,
         ty: CardType::Modifier(CardModifierData {
-            piercing: true,
+            pierce: 3,
             shoot_delay: 0.25,
             ..Default::default()
         }),
@@ -928,12 +1163,14 @@ pub fn piercing() -> Card {
 
 fn acid_puddle() -> Card {
     let projectile = Projectile {
+        ground: true,
         draw_type: ProjectileDrawType::Particle(particle::ACID_PUDDLE),
         extra_size: SPRITE_SIZE,
         modifier_data: CardModifierData {
             speed: 0.0,
             lifetime: 30.0,
-            piercing: true,
+            pierce: INFINITE_PIERCE,
+            rehit_frames: 1,
             damage: hashmap!(DamageType::Acid => 0.35),
             ..Default::default()
         },
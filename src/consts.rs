@@ -25,16 +25,39 @@ pub const GOLD_ROUND_REWARD: u16 = 100;
 pub const DEFAULT_SHOP_SLOTS_HORIZONTAL: usize = 4;
 pub const DEFAULT_SHOP_SLOTS_VERTICAL: usize = 1;
 
+/// Base price used when calculating how much a card sells for
+pub const SELL_BASE_PRICE: f32 = 165.0;
+/// Fraction of a card's price that is refunded when it is sold
+pub const SELL_REFUND_FACTOR: f32 = 0.4;
+
+/// Gold cost of the first reroll of a shop, before being scaled by round
+pub const REROLL_BASE_COST: f32 = 20.0;
+/// How much more each following reroll of the same shop costs
+pub const REROLL_COST_INCREASE: f32 = 20.0;
+
 pub const PREVIEW_FACTOR: f32 = 1.0 / 4.0;
 pub const PREVIEW_WIDTH: f32 = SCREEN_WIDTH * PREVIEW_FACTOR;
 pub const PREVIEW_HEIGHT: f32 = SCREEN_HEIGHT * PREVIEW_FACTOR;
 
 pub const TOWER_MAX_SLOTS: usize = 12;
 
+/// How many divide cards deep copies can be made, before divide cards stop copying
+pub const MAX_DIVIDE_DEPTH: u8 = 2;
+/// Damage factor applied to each copy made by a divide card
+pub const DIVIDE_DAMAGE_FACTOR: f32 = 0.6;
+
+/// Highest level a card can be fused to. Level 0 is a regular card
+pub const MAX_CARD_LEVEL: u8 = 2;
+/// Factor that damage is multiplied by per card level
+pub const UPGRADE_DAMAGE_FACTOR: f32 = 1.35;
+/// How much shoot delay is removed per card level
+pub const UPGRADE_SHOOT_DELAY_REDUCTION: f32 = 0.08;
+
 pub const FREEZE_TIME: u8 = 90;
 
 pub const CARD_INFO_WIDTH: f32 = 64.0 + 32.0;
 pub const CARD_INFO_HEIGHT: f32 = 32.0;
+pub const ENEMY_INFO_WIDTH: f32 = 96.0;
 
 pub const UPGRADE_TIER_CHANCE: u8 = 7;
 
@@ -43,6 +66,33 @@ pub const TOWER_CARDS_MENU_MIN_WIDTH: f32 = 68.0;
 pub const POISON_DAMAGE: f32 = 0.15;
 pub const STUN_IMMUNITY_FRAMES: u8 = 40;
 
+pub const BURN_DAMAGE: f32 = 0.1;
+pub const BURN_TIME: u8 = 60;
+/// Enemies ignited by another burning enemy only burn this long, which is also too short for them to spread it further
+pub const BURN_SPREAD_FRAMES: u8 = 30;
+/// How close an enemy must be to a burning enemy to catch fire
+pub const BURN_SPREAD_DISTANCE: f32 = 10.0;
+/// Percent chance each frame of a burning enemy igniting a nearby enemy
+pub const BURN_SPREAD_CHANCE: u8 = 5;
+/// Pierce value for projectiles that can hit any amount of enemies, like explosions
+pub const INFINITE_PIERCE: u8 = u8::MAX;
+/// Size of cells in the grid used for projectile-enemy collision broad phase
+pub const ENEMY_GRID_CELL_SIZE: f32 = SPRITE_SIZE * 2.0;
+/// Frames after being hit before an enemy's shield starts regenerating
+pub const SHIELD_REGEN_DELAY: u8 = 60;
+/// Frames an enemy stays hasted after being in a speed aura
+pub const HASTE_TIME: u8 = 45;
+/// Damage multiplier of critical hits, before card bonuses
+pub const CRIT_MULTIPLIER: f32 = 2.0;
+/// Frames after being knocked back before an enemy can be knocked back again
+pub const KNOCKBACK_IMMUNITY_FRAMES: u8 = 30;
+/// Frames the gold earned from a kill floats above it
+pub const GOLD_TEXT_LIFETIME: u8 = 30;
+/// Seconds a tower shows it had its cards scrambled
+pub const SCRAMBLE_INDICATOR_TIME: f32 = 0.5;
+/// Distance (in pixels) at which towers with detection reveal camouflaged enemies
+pub const DETECTION_RANGE: f32 = SPRITE_SIZE * 3.0;
+
 pub const COLOR_BEIGE: Color = Color::from_hex(0xb86f50);
 pub const COLOR_BROWN: Color = Color::from_hex(0x743f39);
 pub const COLOR_YELLOW: Color = Color::from_hex(0xffe762);
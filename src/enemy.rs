@@ -1,10 +1,45 @@
-use crate::{cards::DamageType, consts::SPRITE_SIZE, GameAssets};
+use std::{
+    str::FromStr,
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        OnceLock,
+    },
+};
+
+use macroquad::{
+    color::{Color, WHITE},
+    shapes::{draw_circle_lines, draw_rectangle},
+};
+
+use crate::{
+    assets::load_enemy_data,
+    cards::DamageType,
+    consts::{COLOR_BROWN, COLOR_CYAN, COLOR_RED, SHIELD_REGEN_DELAY, SPRITE_SIZE},
+    status::{StackingRule, StatusEffect, StatusEffectType, StatusKind, StatusTick},
+    GameAssets,
+};
 
 #[derive(Clone, Copy)]
-pub enum DamageResistance {
-    None,
-    Partial(DamageType),
-    Full(DamageType),
+/// Factor the damage of each type is multiplied by when dealt to an enemy.
+/// Below 1.0 is a resistance, 0.0 an immunity, and above 1.0 a weakness.
+pub struct DamageMultipliers([f32; 5]);
+impl DamageMultipliers {
+    pub const NONE: Self = Self([1.0; 5]);
+
+    pub fn get(&self, damage_type: DamageType) -> f32 {
+        self.0[damage_type as usize]
+    }
+    pub fn set(&mut self, damage_type: DamageType, multiplier: f32) {
+        self.0[damage_type as usize] = multiplier;
+    }
+    /// Is enemy at all resistant to this damage type?
+    pub fn resists(&self, damage_type: DamageType) -> bool {
+        self.get(damage_type) < 1.0
+    }
+    /// Returns how much damage is actually dealt after resistances and weaknesses
+    pub fn apply(&self, damage_type: DamageType, amount: f32) -> f32 {
+        amount * self.get(damage_type)
+    }
 }
 
 const DEFAULT_ENEMY_TYPE: EnemyType = EnemyType {
@@ -15,10 +50,21 @@ const DEFAULT_ENEMY_TYPE: EnemyType = EnemyType {
     speed: 1.0,
     max_health: 5.0,
     anim_speed: 1.0,
-    damage: 1,
+    leak_damage: 1,
+    bounty: 4,
     payload: EnemyPayload::None,
-    damage_resistance: DamageResistance::None,
+    ability: EnemyAbility::None,
+    ability_cooldown: 60,
+    damage_multipliers: DamageMultipliers::NONE,
+    armour: 0.0,
+    shield: 0.0,
+    shield_regen: 0.0,
     should_flip: true,
+    payload_only: false,
+    flying: false,
+    boss: false,
+    phases: &[],
+    camouflage: false,
 };
 
 #[derive(Clone, Copy)]
@@ -31,66 +77,268 @@ pub struct EnemyType {
     pub speed: f32,
     pub max_health: f32,
     /// How many lives are lost when it finishes path
-    pub damage: u8,
+    pub leak_damage: u8,
+    /// Gold earned when killed, before gold factors
+    pub bounty: u16,
     pub anim_speed: f32,
     pub payload: EnemyPayload,
-    pub damage_resistance: DamageResistance,
+    /// Effect used periodically while alive
+    pub ability: EnemyAbility,
+    /// Frames between each use of ability
+    pub ability_cooldown: u16,
+    pub damage_multipliers: DamageMultipliers,
+    /// Flat amount subtracted from the damage of each type in a hit
+    pub armour: f32,
+    /// Max shield, which absorbs damage from hits before health does
+    pub shield: f32,
+    /// Shield regenerated per frame, once the enemy hasn't been hit for a while
+    pub shield_regen: f32,
     /// If enemy should flip its sprite when moving to the left
     pub should_flip: bool,
+    /// Enemy only appears as a payload of other enemies, so it isn't used for the main menu
+    pub payload_only: bool,
+    /// Flies straight towards the end of the path, over ground hazards
+    pub flying: bool,
+    /// Shows a health bar in the topbar while alive
+    pub boss: bool,
+    /// Phases started as the enemy loses health, ordered by health threshold
+    pub phases: &'static [BossPhase],
+    /// Can't be targeted or hit without detection
+    pub camouflage: bool,
 }
 impl EnemyType {
-    /// Recursively calculates damage sum of self and children
-    pub fn calc_damage(&self) -> u8 {
-        let mut damage = self.damage;
+    /// Recursively calculates leak damage sum of self and children
+    pub fn calc_leak_damage(&self) -> u8 {
+        let mut damage = self.leak_damage;
         if let EnemyPayload::Some(enemy_type, amount) = self.payload {
-            let child_damage = enemy_type.calc_damage();
+            let child_damage = enemy_type.calc_leak_damage();
             damage += child_damage * amount;
         }
         damage
     }
 }
+#[derive(Clone, Copy)]
+/// Changes to an enemy once its health drops to a fraction of max health.
+/// Lets bosses change up the fight without dying and spawning a payload
+pub struct BossPhase {
+    /// Fraction of max health at which the phase starts
+    pub health_threshold: f32,
+    /// Factor speed is multiplied by
+    pub speed_factor: f32,
+    /// Damage type and multiplier replacing the current one
+    pub multiplier: Option<(DamageType, f32)>,
+    /// Enemies spawned when the phase starts
+    pub summon: EnemyPayload,
+    /// Enraged enemies can't be stunned, frozen or knocked back, and use their ability twice as often
+    pub enrage: bool,
+}
 #[derive(Clone, Copy, Default)]
 /// Stores the state of an enemy. Is passed to its children on death
 pub struct EnemyState {
     /// Tracks how far along the path this enemy has moved
     pub score: f32,
-    /// When an enemy is frozen, this value > 0, and ticks down
-    pub freeze_frames: u8,
 }
+/// Source of unique enemy ids
+static NEXT_ENEMY_ID: AtomicU32 = AtomicU32::new(0);
+
 /// A live instance of an enemy
 pub struct Enemy {
+    /// Unique id, used by projectiles to remember which enemies they've hit
+    pub id: u32,
     pub ty: &'static EnemyType,
     pub x: f32,
     pub y: f32,
     pub health: f32,
+    pub shield: f32,
+    /// Frames until shield starts regenerating
+    pub shield_cooldown: u8,
+    /// Frames until ability can be used
+    pub ability_cooldown: u16,
     pub state: EnemyState,
     /// Is enemy moving left?
     pub moving_left: bool,
     /// The gold factor of the last projectile that hit it
     pub gold_factor: Option<f32>,
-    pub poison_frames: u8,
-    /// When an enemy is stunned, this value > 0, and ticks down
-    pub stun_frames: u8,
-    /// After an enemy is stunned, they get a couple frames of immunity.
-    /// This is so that a rapid-firing tower with stun cant literally stop every enemy from moving past it.
-    pub stun_immunity_frames: u8,
+    /// Active status effects, like poison and stun
+    pub statuses: Vec<StatusEffect>,
+    /// How many boss phases have been started
+    pub phase: usize,
+    /// Factor speed is multiplied by, from boss phases
+    pub phase_speed: f32,
+    /// Multipliers of enemy type, changed by boss phases
+    pub damage_multipliers: DamageMultipliers,
+    pub enraged: bool,
+    /// Is camouflaged enemy revealed by a nearby tower with detection
+    pub detected: bool,
 }
 impl Enemy {
     pub fn new(ty: &'static EnemyType, x: f32, y: f32, state: EnemyState) -> Self {
         Self {
+            id: NEXT_ENEMY_ID.fetch_add(1, Ordering::Relaxed),
             ty,
             x,
             y,
             health: ty.max_health,
+            shield: ty.shield,
+            shield_cooldown: 0,
+            ability_cooldown: ty.ability_cooldown,
             state,
             moving_left: false,
             gold_factor: None,
-            poison_frames: 0,
-            stun_frames: 0,
-            stun_immunity_frames: 0,
+            statuses: Vec::new(),
+            phase: 0,
+            phase_speed: 1.0,
+            damage_multipliers: ty.damage_multipliers,
+            enraged: false,
+            detected: false,
+        }
+    }
+    /// Can enemy be targeted and hit? Camouflaged enemies need to be detected first
+    pub fn is_visible(&self, detection: bool) -> bool {
+        !self.ty.camouflage || self.detected || detection
+    }
+    /// Deals damage of a hit, after multipliers and armour. Shield absorbs the damage before health does
+    pub fn take_hit(&mut self, damage_type: DamageType, amount: f32) {
+        let amount = (self.damage_multipliers.apply(damage_type, amount) - self.ty.armour).max(0.0);
+        if amount <= 0.0 {
+            return;
+        }
+        let absorbed = amount.min(self.shield);
+        self.shield -= absorbed;
+        self.health -= amount - absorbed;
+        self.shield_cooldown = SHIELD_REGEN_DELAY;
+    }
+    pub fn update_shield(&mut self) {
+        if self.shield_cooldown > 0 {
+            self.shield_cooldown -= 1;
+        } else {
+            self.shield = (self.shield + self.ty.shield_regen).min(self.ty.shield);
         }
     }
-    pub fn draw(&self, assets: &GameAssets) {
+    /// Ticks down ability cooldown. Returns the ability if it should be used this frame
+    pub fn update_ability(&mut self) -> Option<EnemyAbility> {
+        // enemies killed this frame haven't been removed yet
+        if matches!(self.ty.ability, EnemyAbility::None) || self.health <= 0.0 {
+            return None;
+        }
+        // stunned enemies can't use their abilities
+        if self.get_status(StatusKind::Stun).is_some() {
+            return None;
+        }
+        if self.ability_cooldown > 0 {
+            self.ability_cooldown -= 1;
+            return None;
+        }
+        self.ability_cooldown = if self.enraged {
+            self.ty.ability_cooldown / 2
+        } else {
+            self.ty.ability_cooldown
+        };
+        Some(self.ty.ability)
+    }
+    /// Starts all boss phases whose health threshold has been reached. Returns the enemies they summon
+    pub fn update_phases(&mut self) -> Vec<(&'static EnemyType, u8)> {
+        let mut summons = Vec::new();
+        while let Some(phase) = self.ty.phases.get(self.phase) {
+            if self.health > self.ty.max_health * phase.health_threshold {
+                break;
+            }
+            self.phase += 1;
+            self.phase_speed *= phase.speed_factor;
+            if let Some((damage_type, multiplier)) = phase.multiplier {
+                self.damage_multipliers.set(damage_type, multiplier);
+            }
+            if let EnemyPayload::Some(enemy_type, amount) = phase.summon {
+                summons.push((enemy_type, amount));
+            }
+            if phase.enrage {
+                self.enraged = true;
+                self.remove_status(StatusKind::Stun);
+                self.remove_status(StatusKind::Freeze);
+            }
+        }
+        summons
+    }
+    /// Gets a status effect of kind, if it is active
+    pub fn get_status(&self, kind: StatusKind) -> Option<&StatusEffect> {
+        self.statuses
+            .iter()
+            .find(|status| status.ty.kind == kind && status.frames > 0)
+    }
+    /// Returns false if the status couldn't be applied, because of resistance or immunity
+    pub fn apply_status(&mut self, ty: &'static StatusEffectType, frames: u8) -> bool {
+        if self.enraged
+            && matches!(
+                ty.kind,
+                StatusKind::Stun | StatusKind::Freeze | StatusKind::Knockback
+            )
+        {
+            return false;
+        }
+        if ty
+            .resisted_by
+            .is_some_and(|damage_type| self.damage_multipliers.resists(damage_type))
+        {
+            return false;
+        }
+        if let Some(status) = self.statuses.iter_mut().find(|f| f.ty.kind == ty.kind) {
+            // statuses with immunity frames can't be reapplied until the immunity is over
+            if ty.immunity_frames > 0 {
+                return false;
+            }
+            status.frames = match ty.stacking {
+                StackingRule::Add(max) => status.frames.saturating_add(frames).min(max),
+                StackingRule::Refresh | StackingRule::Permanent => status.frames.max(frames),
+            };
+            return true;
+        }
+        let frames = match ty.stacking {
+            StackingRule::Add(max) => frames.min(max),
+            StackingRule::Refresh | StackingRule::Permanent => frames,
+        };
+        self.statuses.push(StatusEffect {
+            ty,
+            frames,
+            immunity_frames: ty.immunity_frames,
+        });
+        true
+    }
+    pub fn remove_status(&mut self, kind: StatusKind) {
+        self.statuses.retain(|status| status.ty.kind != kind);
+    }
+    /// Updates all status effects by one frame. Returns the factor the enemy's speed should be multiplied by.
+    pub fn tick_statuses(&mut self) -> f32 {
+        let mut slowest: f32 = 1.0;
+        let mut fastest: f32 = 1.0;
+        let mut damage = 0.0;
+        for status in self.statuses.iter_mut() {
+            if status.frames > 0 {
+                if !matches!(status.ty.stacking, StackingRule::Permanent) {
+                    status.frames -= 1;
+                }
+                if let StatusTick::Damage(damage_type, amount) = status.ty.tick {
+                    damage += self.damage_multipliers.apply(damage_type, amount);
+                }
+                // the slowest status decides the speed, which the fastest status then speeds up
+                let speed_factor = if self.ty.size == 1 {
+                    status.ty.speed_factor
+                } else {
+                    status.ty.large_speed_factor
+                };
+                slowest = slowest.min(speed_factor);
+                fastest = fastest.max(speed_factor);
+            }
+            // only subtract immunity frames after the status has wore off
+            else {
+                status.immunity_frames = status.immunity_frames.saturating_sub(1);
+            }
+        }
+        self.statuses
+            .retain(|status| status.frames > 0 || status.immunity_frames > 0);
+        self.health -= damage;
+        slowest * fastest
+    }
+    pub fn draw(&self, assets: &GameAssets, show_health_bar: bool) {
         let extra_size = self.ty.size - 1;
         let ground_offset = 2.0 + extra_size as f32 * SPRITE_SIZE;
         let anim_frame =
@@ -99,7 +347,12 @@ impl Enemy {
         if self.moving_left && self.ty.should_flip {
             flipped = true;
         }
-        let (centre_x, _) = self.get_centre();
+        let (centre_x, centre_y) = self.get_centre();
+        let color = if self.is_visible(false) {
+            WHITE
+        } else {
+            Color { a: 0.4, ..WHITE }
+        };
         for i in 0..self.ty.size {
             for j in 0..self.ty.size {
                 let mut sprite = self.ty.sprite + anim_frame * self.ty.size;
@@ -109,16 +362,38 @@ impl Enemy {
                     sprite += j;
                 }
                 sprite += i * 32;
-                assets.icon_sheet.draw_tile(
+                assets.icon_sheet.draw_tile_tinted(
                     self.x + j as f32 * SPRITE_SIZE - extra_size as f32 * SPRITE_SIZE / 2.0,
                     self.y + i as f32 * SPRITE_SIZE - ground_offset,
                     sprite,
                     flipped,
                     0.0,
+                    color,
                 );
             }
         }
-        if self.state.freeze_frames > 0 {
+        if show_health_bar && self.health < self.ty.max_health {
+            let width = SPRITE_SIZE * self.ty.size as f32 - 2.0;
+            let x = centre_x - width / 2.0;
+            let y = self.y - ground_offset - 2.0;
+            let health = (self.health / self.ty.max_health).clamp(0.0, 1.0);
+            draw_rectangle(x, y, width, 1.0, COLOR_BROWN);
+            draw_rectangle(x, y, width * health, 1.0, COLOR_RED);
+        }
+        if self.shield > 0.0 {
+            let color = Color {
+                a: 0.3 + 0.5 * self.shield / self.ty.shield,
+                ..COLOR_CYAN
+            };
+            draw_circle_lines(
+                centre_x,
+                centre_y - 2.0,
+                SPRITE_SIZE * self.ty.size as f32 / 2.0 + 1.0,
+                1.0,
+                color,
+            );
+        }
+        if self.get_status(StatusKind::Freeze).is_some() {
             for j in 0..self.ty.size {
                 assets.particle_sheet.draw_tile(
                     self.x + j as f32 * SPRITE_SIZE - extra_size as f32 * SPRITE_SIZE / 2.0,
@@ -129,8 +404,18 @@ impl Enemy {
                 );
             }
         }
-        if self.stun_frames > 0 {
-            let anim_frame = self.stun_frames % 3;
+        if let Some(burning) = self.get_status(StatusKind::Burning) {
+            let anim_frame = burning.frames as usize / 3 % 3;
+            assets.particle_sheet.draw_tile(
+                centre_x - SPRITE_SIZE / 2.0,
+                self.y - ground_offset + SPRITE_SIZE / 2.0,
+                38 + anim_frame,
+                false,
+                -std::f32::consts::PI / 2.0,
+            );
+        }
+        if let Some(stun) = self.get_status(StatusKind::Stun) {
+            let anim_frame = stun.frames % 3;
             assets.particle_sheet.draw_tile(
                 centre_x - SPRITE_SIZE / 2.0,
                 self.y - SPRITE_SIZE / 2.0,
@@ -148,6 +433,23 @@ impl Enemy {
     }
 }
 
+#[derive(Clone, Copy)]
+pub enum EnemyAbility {
+    None,
+    /// Restores health of enemies in range, including itself. Amount, range
+    Heal(f32, f32),
+    /// Spawns enemies at its position along the path. Type, amount
+    Summon(&'static EnemyType, u8),
+    /// Hastes enemies in range. Range
+    SpeedAura(f32),
+    /// Removes poison and freeze from enemies in range. Range
+    Cleanse(f32),
+    /// Stops towers in range from shooting. Range, seconds
+    Disable(f32, f32),
+    /// Moves the card index of towers in range to a random card. Range
+    Scramble(f32),
+}
+
 #[derive(Clone, Copy)]
 pub enum EnemyPayload {
     None,
@@ -155,452 +457,336 @@ pub enum EnemyPayload {
     Some(&'static EnemyType, u8),
 }
 
-static BABY_SPIDER: EnemyType = EnemyType {
-    name: "baby_spider",
-    sprite: 2 * 32 + 4,
-    anim_length: 2,
-    speed: 2.0,
-    anim_speed: 1.6,
-    max_health: 1.0,
-    ..DEFAULT_ENEMY_TYPE
-};
-static FIRE_MITE: EnemyType = EnemyType {
-    name: "fire_mite",
-    sprite: 4 * 32 + 12,
-    anim_length: 2,
-    speed: 2.0,
-    anim_speed: 1.6,
-    max_health: 5.0,
-    damage_resistance: DamageResistance::Full(DamageType::Burn),
-    ..DEFAULT_ENEMY_TYPE
-};
+/// All enemy types, loaded from data/enemies.txt and interned on first use
+static ENEMY_TYPES: OnceLock<Vec<&'static EnemyType>> = OnceLock::new();
 
-static HORSEY: EnemyType = EnemyType {
-    name: "horsey",
-    sprite: 6 * 32 + 8,
-    anim_length: 2,
-    speed: 1.2,
-    anim_speed: 0.25,
-    size: 2,
-    damage: 1,
-    max_health: 8.0,
-    ..DEFAULT_ENEMY_TYPE
-};
-static SLIME: EnemyType = EnemyType {
-    name: "slime",
-    sprite: 3 * 32,
-    anim_length: 3,
-    damage: 2,
-    speed: 0.75,
-    max_health: 6.0,
-    damage_resistance: DamageResistance::Partial(DamageType::Pierce),
-    ..DEFAULT_ENEMY_TYPE
-};
-static SPIDER: EnemyType = EnemyType {
-    name: "spider",
-    sprite: 2 * 32,
-    anim_length: 2,
-    speed: 1.0,
-    max_health: 3.0,
-    ..DEFAULT_ENEMY_TYPE
-};
-static BIG_KNIGHT: EnemyType = EnemyType {
-    name: "big_knight",
-    sprite: 12 * 32 + 4,
-    anim_length: 2,
-    speed: 0.6,
-    damage: 14,
-    size: 2,
-    max_health: 85.0,
-    damage_resistance: DamageResistance::Full(DamageType::Pierce),
-    ..DEFAULT_ENEMY_TYPE
-};
-static GIGA_CULTIST: EnemyType = EnemyType {
-    name: "giga_cultist",
-    sprite: 12 * 32 + 8 + 2,
-    anim_length: 2,
-    damage: 20,
-    speed: 1.0,
-    size: 2,
-    max_health: 35.0,
-    damage_resistance: DamageResistance::Full(DamageType::Magic),
-    ..DEFAULT_ENEMY_TYPE
-};
-static BIG_SLIME: EnemyType = EnemyType {
-    name: "big_slime",
-    sprite: 3 * 32 + 3,
-    anim_length: 3,
-    damage: 2,
-    speed: 0.4,
-    max_health: 20.0,
-    damage_resistance: DamageResistance::Partial(DamageType::Pierce),
-    payload: EnemyPayload::Some(&SLIME, 2),
-    ..DEFAULT_ENEMY_TYPE
-};
-static BIG_FIRE_SLIME: EnemyType = EnemyType {
-    name: "big_fire_slime",
-    sprite: 2 * 32 + 8,
-    speed: 0.37,
-    damage_resistance: DamageResistance::Full(DamageType::Burn),
-    payload: EnemyPayload::Some(&BIG_SLIME, 2),
-    ..BIG_SLIME
-};
-static INJURED_TROLL: EnemyType = EnemyType {
-    name: "injured_troll",
-    sprite: 17 * 32,
-    size: 3,
-    anim_length: 6,
-    speed: 0.2,
-    damage: 20,
-    max_health: 250.0,
-    anim_speed: 2.0,
-    damage_resistance: DamageResistance::Full(DamageType::Acid),
-    ..DEFAULT_ENEMY_TYPE
-};
-static TROLL: EnemyType = EnemyType {
-    name: "troll",
-    sprite: 14 * 32,
-    size: 3,
-    anim_length: 6,
-    speed: 0.4,
-    damage: 0,
-    damage_resistance: DamageResistance::Full(DamageType::Acid),
-    payload: EnemyPayload::Some(&INJURED_TROLL, 1),
-    ..INJURED_TROLL
-};
-static GIGA_SLIME: EnemyType = EnemyType {
-    name: "giga_slime",
-    sprite: 2 * 32 + 11,
-    anim_length: 5,
-    damage: 0,
-    speed: 0.35,
-    size: 2,
-    max_health: 261.0,
-    damage_resistance: DamageResistance::Partial(DamageType::Pierce),
-    payload: EnemyPayload::Some(&BIG_FIRE_SLIME, 8),
-    ..DEFAULT_ENEMY_TYPE
-};
-static BLUE_MUSHROOM: EnemyType = EnemyType {
-    sprite: 27,
-    max_health: 125.0,
-    ..YELLOW_MUSHROOM
-};
-static YELLOW_MUSHROOM: EnemyType = EnemyType {
-    name: "mushroom",
-    sprite: 32 + 27,
-    anim_length: 1,
-    damage: 0,
-    speed: 0.0,
-    max_health: 16.0,
-    ..DEFAULT_ENEMY_TYPE
-};
-static GREEN_MUSHROOM: EnemyType = EnemyType {
-    sprite: 2 * 32 + 27,
-    max_health: 24.0,
-    ..YELLOW_MUSHROOM
-};
-static BLUE_MUSHROOM_GUY: EnemyType = EnemyType {
-    name: "blue_mushroom_guy",
-    sprite: 28,
-    damage: 5,
-    max_health: 95.0,
-    damage_resistance: DamageResistance::Partial(DamageType::Magic),
-    payload: EnemyPayload::Some(&BLUE_MUSHROOM, 1),
-    ..YELLOW_MUSHROOM_GUY
-};
-static YELLOW_MUSHROOM_GUY: EnemyType = EnemyType {
-    name: "yellow_mushroom_guy",
-    sprite: 32 + 28,
-    anim_length: 4,
-    damage: 2,
-    speed: 1.2,
-    anim_speed: 1.0 / 1.2 * 0.5,
-    payload: EnemyPayload::Some(&YELLOW_MUSHROOM, 1),
-    max_health: 7.0,
-    ..DEFAULT_ENEMY_TYPE
-};
-static GREEN_MUSHROOM_GUY: EnemyType = EnemyType {
-    name: "green_mushroom_guy",
-    sprite: 2 * 32 + 28,
-    damage: 3,
-    max_health: 20.0,
-    damage_resistance: DamageResistance::Partial(DamageType::Acid),
-    payload: EnemyPayload::Some(&GREEN_MUSHROOM, 1),
-    ..YELLOW_MUSHROOM_GUY
-};
-static BIG_MUSHROOM: EnemyType = EnemyType {
-    name: "big_mushroom",
-    sprite: 15 * 32 + 30,
-    damage: 0,
-    speed: 0.0,
-    size: 2,
-    anim_length: 1,
-    max_health: 250.0,
-    payload: EnemyPayload::Some(&YELLOW_MUSHROOM_GUY, 20),
-    ..DEFAULT_ENEMY_TYPE
-};
-static BIG_INJURED_MUSHROOM_GUY: EnemyType = EnemyType {
-    name: "big_mushroom_guy",
-    sprite: 17 * 32 + 18,
-    speed: 0.5,
-    anim_speed: 0.5,
-    size: 3,
-    anim_length: 4,
-    damage: 0,
-    max_health: 150.0,
-    payload: EnemyPayload::Some(&BIG_MUSHROOM, 1),
-    ..DEFAULT_ENEMY_TYPE
-};
-static BIG_MUSHROOM_GUY: EnemyType = EnemyType {
-    name: "big_mushroom_guy",
-    sprite: 14 * 32 + 18,
-    speed: 0.5,
-    damage: 5,
-    max_health: 150.0,
-    payload: EnemyPayload::Some(&BIG_INJURED_MUSHROOM_GUY, 1),
-    ..BIG_INJURED_MUSHROOM_GUY
-};
-static KNIGHT: EnemyType = EnemyType {
-    name: "knight",
-    sprite: 11 * 32 + 2,
-    anim_length: 2,
-    speed: 1.0,
-    damage: 4,
-    max_health: 15.0,
-    damage_resistance: DamageResistance::Full(DamageType::Pierce),
-    ..DEFAULT_ENEMY_TYPE
-};
-static CARRIAGE: EnemyType = EnemyType {
-    name: "carriage",
-    sprite: 9 * 32 + 12,
-    speed: 2.0,
-    size: 3,
-    damage: 0,
-    max_health: 150.0,
-    payload: EnemyPayload::Some(&KNIGHT, 5),
-    ..DEFAULT_ENEMY_TYPE
-};
-pub static ENEMY_TYPES: &[EnemyType] = &[
-    HORSEY,
-    EnemyType {
-        name: "horsey_rider",
-        sprite: 6 * 32 + 4,
-        speed: 1.0,
-        damage: 5,
-        max_health: 15.0,
-        anim_length: 2,
-        payload: EnemyPayload::Some(&HORSEY, 1),
-        damage_resistance: DamageResistance::Full(DamageType::Pierce),
-        ..HORSEY
-    },
-    SPIDER,
-    EnemyType {
-        name: "armored_spider",
-        sprite: 2 * 32 + 2,
-        anim_length: 2,
-        damage: 9,
-        speed: 0.5,
-        max_health: 34.0,
-        damage_resistance: DamageResistance::Full(DamageType::Pierce),
-        payload: EnemyPayload::Some(&SPIDER, 1),
-        ..DEFAULT_ENEMY_TYPE
-    },
-    BABY_SPIDER,
-    EnemyType {
-        name: "spider_warrior",
-        sprite: 2 * 32 + 6,
-        anim_length: 2,
-        damage: 10,
-        speed: 1.0,
-        max_health: 20.0,
-        ..DEFAULT_ENEMY_TYPE
-    },
-    EnemyType {
-        name: "big_spider",
-        sprite: 6 * 32,
-        anim_length: 2,
-        speed: 1.0 / 4.0,
-        max_health: 30.0,
-        payload: EnemyPayload::Some(&BABY_SPIDER, 4),
-        size: 2,
-        ..DEFAULT_ENEMY_TYPE
-    },
-    SLIME,
-    BIG_SLIME,
-    BIG_FIRE_SLIME,
-    EnemyType {
-        name: "fire_slime",
-        sprite: 3 * 32 + 6,
-        anim_length: 3,
-        damage: 2,
-        speed: 0.6,
-        max_health: 24.0,
-        damage_resistance: DamageResistance::Partial(DamageType::Burn),
-        payload: EnemyPayload::Some(&SLIME, 1),
-        ..DEFAULT_ENEMY_TYPE
-    },
-    EnemyType {
-        name: "slime_car",
-        sprite: 3 * 32 + 9,
-        anim_length: 2,
-        damage: 2,
-        speed: 2.0,
-        anim_speed: 1.6,
-        max_health: 6.0,
-        damage_resistance: DamageResistance::Partial(DamageType::Pierce),
-        ..DEFAULT_ENEMY_TYPE
-    },
-    EnemyType {
-        name: "fire_cat",
-        sprite: 4 * 32,
-        anim_length: 2,
-        speed: 1.0,
-        max_health: 5.0,
-        damage_resistance: DamageResistance::Full(DamageType::Burn),
-        ..DEFAULT_ENEMY_TYPE
-    },
-    EnemyType {
-        name: "fire_golem",
-        sprite: 4 * 32 + 2,
-        anim_length: 5,
-        damage: 15,
-        speed: 0.25,
-        size: 2,
-        max_health: 65.0,
-        damage_resistance: DamageResistance::Partial(DamageType::Burn),
-        payload: EnemyPayload::Some(&FIRE_MITE, 5),
-        ..DEFAULT_ENEMY_TYPE
-    },
-    EnemyType {
-        name: "skeleton",
-        sprite: 5 * 32,
-        anim_length: 2,
-        anim_speed: 0.8,
-        speed: 1.7,
-        max_health: 6.0,
-        ..DEFAULT_ENEMY_TYPE
-    },
-    EnemyType {
-        name: "ice_slug",
-        sprite: 8 * 32,
-        anim_length: 2,
-        speed: 1.0,
-        max_health: 15.0,
-        damage_resistance: DamageResistance::Full(DamageType::Cold),
-        ..DEFAULT_ENEMY_TYPE
-    },
-    EnemyType {
-        name: "snow_ball",
-        sprite: 8 * 32 + 2,
-        anim_length: 6,
-        speed: 2.0,
-        anim_speed: 0.5,
-        max_health: 13.0,
-        damage_resistance: DamageResistance::Full(DamageType::Cold),
-        ..DEFAULT_ENEMY_TYPE
-    },
-    EnemyType {
-        name: "giga_ice_slug",
-        sprite: 9 * 32,
-        anim_length: 2,
-        speed: 0.42,
-        damage: 15,
-        size: 2,
-        max_health: 77.0,
-        damage_resistance: DamageResistance::Full(DamageType::Cold),
-        ..DEFAULT_ENEMY_TYPE
-    },
-    EnemyType {
-        name: "cultist",
-        sprite: 11 * 32,
-        anim_length: 2,
-        speed: 1.2,
-        damage: 2,
-        max_health: 20.0,
-        damage_resistance: DamageResistance::Full(DamageType::Magic),
-        ..DEFAULT_ENEMY_TYPE
-    },
-    KNIGHT,
-    CARRIAGE,
-    EnemyType {
-        name: "archer",
-        sprite: 11 * 32 + 4,
-        anim_length: 2,
-        speed: 1.2,
-        damage: 4,
-        max_health: 10.0,
-        ..DEFAULT_ENEMY_TYPE
-    },
-    EnemyType {
-        name: "big_knight_shield",
-        sprite: 12 * 32,
-        anim_length: 2,
-        speed: 0.55,
-        damage: 0,
-        size: 2,
-        max_health: 40.0,
-        payload: EnemyPayload::Some(&BIG_KNIGHT, 1),
-        damage_resistance: DamageResistance::Full(DamageType::Burn),
-        ..DEFAULT_ENEMY_TYPE
-    },
-    EnemyType {
-        name: "dragon",
-        sprite: 9 * 32 + 4,
-        anim_length: 4,
-        damage: 15,
-        speed: 0.45,
-        size: 2,
-        max_health: 25.0,
-        damage_resistance: DamageResistance::Full(DamageType::Burn),
-        ..DEFAULT_ENEMY_TYPE
-    },
-    EnemyType {
-        name: "giga_cultist_shield",
-        sprite: 12 * 32 + 8,
-        anim_length: 1,
-        damage: 20,
-        speed: 1.0,
-        size: 2,
-        max_health: 61.0,
-        damage_resistance: DamageResistance::Full(DamageType::Pierce),
-        payload: EnemyPayload::Some(&GIGA_CULTIST, 1),
-        ..DEFAULT_ENEMY_TYPE
-    },
-    GIGA_SLIME,
-    TROLL,
-    EnemyType {
-        name: "armored_troll",
-        sprite: 20 * 32,
-        speed: 0.3,
-        max_health: 120.0,
-        damage_resistance: DamageResistance::Full(DamageType::Pierce),
-        payload: EnemyPayload::Some(&TROLL, 1),
-        ..TROLL
-    },
-    EnemyType {
-        name: "magicbird",
-        sprite: 5 * 32 + 12,
-        speed: 1.0,
-        anim_length: 2,
-        max_health: 15.0,
-        damage: 2,
-        damage_resistance: DamageResistance::Partial(DamageType::Magic),
-        ..DEFAULT_ENEMY_TYPE
-    },
-    EnemyType {
-        name: "ultra_slime",
-        sprite: 6 * 32 + 12,
-        speed: 0.30,
-        anim_length: 6,
-        size: 3,
-        max_health: 350.0,
-        anim_speed: 1.5,
-        damage: 0,
-        payload: EnemyPayload::Some(&GIGA_SLIME, 2),
-        ..GIGA_SLIME
-    },
-    BLUE_MUSHROOM_GUY,
-    YELLOW_MUSHROOM_GUY,
-    GREEN_MUSHROOM_GUY,
-    BIG_MUSHROOM_GUY,
-];
+/// Gets all enemy types, in the order they are defined in the data file
+pub fn enemy_types() -> &'static [&'static EnemyType] {
+    ENEMY_TYPES.get_or_init(|| parse_enemy_types(&load_enemy_data()))
+}
+/// Enemy type as parsed from the data file, before payload names have been resolved
+struct EnemyDefinition {
+    ty: EnemyType,
+    payload: Option<(String, u8)>,
+    /// Name and amount of enemies spawned by summon ability
+    summon: Option<(String, u8)>,
+    /// Boss phases, with the name and amount of enemies they summon
+    phases: Vec<(BossPhase, Option<(String, u8)>)>,
+}
+
+fn bad_enemy_data(line_number: usize, line: &str, message: &str) -> ! {
+    panic!("bad enemy data at line {line_number}: {message} ('{line}')")
+}
+fn parse_field<T: FromStr>(value: &str, line_number: usize, line: &str) -> T {
+    value
+        .parse()
+        .unwrap_or_else(|_| bad_enemy_data(line_number, line, "invalid value"))
+}
+fn parse_damage_type(value: &str, line_number: usize, line: &str) -> DamageType {
+    match value {
+        "magic" => DamageType::Magic,
+        "pierce" => DamageType::Pierce,
+        "burn" => DamageType::Burn,
+        "cold" => DamageType::Cold,
+        "acid" => DamageType::Acid,
+        _ => bad_enemy_data(line_number, line, "unknown damage type"),
+    }
+}
+
+/// Parses a health threshold followed by any amount of effects, like '0.5 speed 1.5 summon spider 4 enrage'
+fn parse_phase(value: &str, line_number: usize, line: &str) -> (BossPhase, Option<(String, u8)>) {
+    let mut args = value.split_whitespace();
+    let threshold = args.next().unwrap_or_default();
+    let mut phase = BossPhase {
+        health_threshold: parse_field(threshold, line_number, line),
+        speed_factor: 1.0,
+        multiplier: None,
+        summon: EnemyPayload::None,
+        enrage: false,
+    };
+    let mut summon = None;
+    // effects are parsed until there are no arguments left
+    while let Some(effect) = args.next() {
+        let mut next_arg = || {
+            args.next()
+                .unwrap_or_else(|| bad_enemy_data(line_number, line, "missing phase argument"))
+        };
+        match effect {
+            "speed" => phase.speed_factor = parse_field(next_arg(), line_number, line),
+            "multiplier" => {
+                let damage_type = parse_damage_type(next_arg(), line_number, line);
+                let multiplier = parse_field(next_arg(), line_number, line);
+                phase.multiplier = Some((damage_type, multiplier));
+            }
+            "summon" => {
+                // resolved to an actual enemy type once all enemies are parsed
+                let name = next_arg().to_string();
+                summon = Some((name, parse_field(next_arg(), line_number, line)));
+            }
+            "enrage" => phase.enrage = true,
+            _ => bad_enemy_data(line_number, line, "unknown phase effect"),
+        }
+    }
+    (phase, summon)
+}
+
+fn parse_enemy_types(data: &str) -> Vec<&'static EnemyType> {
+    let mut definitions: Vec<EnemyDefinition> = Vec::new();
+    for (index, line) in data.lines().enumerate() {
+        let line_number = index + 1;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        // start of new enemy, optionally based on a previous one
+        if let Some(header) = line.strip_prefix('[').and_then(|f| f.strip_suffix(']')) {
+            let (name, base) = match header.split_once(':') {
+                Some((name, base)) => (name.trim(), Some(base.trim())),
+                None => (header.trim(), None),
+            };
+            if definitions.iter().any(|f| f.ty.name == name) {
+                bad_enemy_data(line_number, line, "enemy name already used");
+            }
+            let mut definition = match base {
+                Some(base) => {
+                    let Some(base) = definitions.iter().find(|f| f.ty.name == base) else {
+                        bad_enemy_data(line_number, line, "unknown base enemy");
+                    };
+                    EnemyDefinition {
+                        ty: EnemyType {
+                            payload_only: false,
+                            ..base.ty
+                        },
+                        payload: base.payload.clone(),
+                        summon: base.summon.clone(),
+                        phases: base.phases.clone(),
+                    }
+                }
+                None => EnemyDefinition {
+                    ty: DEFAULT_ENEMY_TYPE,
+                    payload: None,
+                    summon: None,
+                    phases: Vec::new(),
+                },
+            };
+            definition.ty.name = name.to_string().leak();
+            definitions.push(definition);
+            continue;
+        }
+
+        let Some((key, value)) = line.split_once('=') else {
+            bad_enemy_data(line_number, line, "expected 'field = value'");
+        };
+        let Some(definition) = definitions.last_mut() else {
+            bad_enemy_data(line_number, line, "field before any enemy");
+        };
+        let value = value.trim();
+        let ty = &mut definition.ty;
+        match key.trim() {
+            "sprite" => {
+                let Some((row, column)) = value.split_once(' ') else {
+                    bad_enemy_data(line_number, line, "expected 'row column'");
+                };
+                let row: usize = parse_field(row, line_number, line);
+                let column: usize = parse_field(column.trim(), line_number, line);
+                ty.sprite = row * 32 + column;
+            }
+            "anim_length" => ty.anim_length = parse_field(value, line_number, line),
+            "size" => ty.size = parse_field(value, line_number, line),
+            "speed" => ty.speed = parse_field(value, line_number, line),
+            "max_health" => ty.max_health = parse_field(value, line_number, line),
+            "leak_damage" => ty.leak_damage = parse_field(value, line_number, line),
+            "bounty" => ty.bounty = parse_field(value, line_number, line),
+            "anim_speed" => ty.anim_speed = parse_field(value, line_number, line),
+            "armour" => ty.armour = parse_field(value, line_number, line),
+            "shield" => ty.shield = parse_field(value, line_number, line),
+            "shield_regen" => ty.shield_regen = parse_field(value, line_number, line),
+            "should_flip" => ty.should_flip = parse_field(value, line_number, line),
+            "flying" => ty.flying = parse_field(value, line_number, line),
+            "camouflage" => ty.camouflage = parse_field(value, line_number, line),
+            "boss" => ty.boss = parse_field(value, line_number, line),
+            "phase" => {
+                let phase = parse_phase(value, line_number, line);
+                definition.phases.push(phase);
+            }
+            "payload_only" => ty.payload_only = parse_field(value, line_number, line),
+            "payload" => {
+                let Some((name, amount)) = value.split_once(' ') else {
+                    bad_enemy_data(line_number, line, "expected 'name amount'");
+                };
+                let amount = parse_field(amount.trim(), line_number, line);
+                definition.payload = Some((name.to_string(), amount));
+            }
+            "ability" => {
+                let args: Vec<&str> = value.split_whitespace().collect();
+                definition.summon = None;
+                ty.ability = match args.as_slice() {
+                    ["none"] => EnemyAbility::None,
+                    ["heal", amount, range] => EnemyAbility::Heal(
+                        parse_field(amount, line_number, line),
+                        parse_field(range, line_number, line),
+                    ),
+                    ["summon", name, amount] => {
+                        // resolved to an actual enemy type once all enemies are parsed
+                        definition.summon =
+                            Some((name.to_string(), parse_field(amount, line_number, line)));
+                        EnemyAbility::None
+                    }
+                    ["speed_aura", range] => {
+                        EnemyAbility::SpeedAura(parse_field(range, line_number, line))
+                    }
+                    ["cleanse", range] => {
+                        EnemyAbility::Cleanse(parse_field(range, line_number, line))
+                    }
+                    ["disable", range, seconds] => EnemyAbility::Disable(
+                        parse_field(range, line_number, line),
+                        parse_field(seconds, line_number, line),
+                    ),
+                    ["scramble", range] => {
+                        EnemyAbility::Scramble(parse_field(range, line_number, line))
+                    }
+                    _ => bad_enemy_data(line_number, line, "unknown ability"),
+                };
+            }
+            "ability_cooldown" => ty.ability_cooldown = parse_field(value, line_number, line),
+            "multiplier" => {
+                let Some((damage_type, multiplier)) = value.split_once(' ') else {
+                    bad_enemy_data(line_number, line, "expected 'damage_type multiplier'");
+                };
+                let damage_type = parse_damage_type(damage_type, line_number, line);
+                let multiplier = parse_field(multiplier.trim(), line_number, line);
+                ty.damage_multipliers.set(damage_type, multiplier);
+            }
+            _ => bad_enemy_data(line_number, line, "unknown field"),
+        }
+    }
+
+    let mut interned = vec![None; definitions.len()];
+    (0..definitions.len())
+        .map(|index| intern_enemy_type(index, &definitions, &mut interned, &mut Vec::new()))
+        .collect()
+}
+/// Leaks enemy type so it can be referenced like a static, after recursively doing the same for its payload
+fn intern_enemy_type(
+    index: usize,
+    definitions: &[EnemyDefinition],
+    interned: &mut Vec<Option<&'static EnemyType>>,
+    visiting: &mut Vec<usize>,
+) -> &'static EnemyType {
+    if let Some(ty) = interned[index] {
+        return ty;
+    }
+    let definition = &definitions[index];
+    // enemy types are interned after what they spawn, so an enemy can't end up spawning itself
+    if let Some(start) = visiting.iter().position(|f| *f == index) {
+        let cycle: Vec<&str> = visiting[start..]
+            .iter()
+            .chain([&index])
+            .map(|f| definitions[*f].ty.name)
+            .collect();
+        panic!(
+            "enemy {} spawns itself through payloads, summons or boss phases ({})",
+            definition.ty.name,
+            cycle.join(" -> ")
+        );
+    }
+    visiting.push(index);
+    let mut ty = definition.ty;
+    if let Some((name, amount)) = &definition.payload {
+        let Some(payload_index) = definitions.iter().position(|f| f.ty.name == name) else {
+            panic!("unknown payload {name} of enemy {}", definition.ty.name);
+        };
+        let payload = intern_enemy_type(payload_index, definitions, interned, visiting);
+        ty.payload = EnemyPayload::Some(payload, *amount);
+    }
+    if let Some((name, amount)) = &definition.summon {
+        let Some(summon_index) = definitions.iter().position(|f| f.ty.name == name) else {
+            panic!("unknown summon {name} of enemy {}", definition.ty.name);
+        };
+        let summon = intern_enemy_type(summon_index, definitions, interned, visiting);
+        ty.ability = EnemyAbility::Summon(summon, *amount);
+    }
+    let mut phases = Vec::new();
+    for (phase, summon) in &definition.phases {
+        let mut phase = *phase;
+        if let Some((name, amount)) = summon {
+            let Some(summon_index) = definitions.iter().position(|f| f.ty.name == name) else {
+                panic!(
+                    "unknown phase summon {name} of enemy {}",
+                    definition.ty.name
+                );
+            };
+            let summon = intern_enemy_type(summon_index, definitions, interned, visiting);
+            phase.summon = EnemyPayload::Some(summon, *amount);
+        }
+        phases.push(phase);
+    }
+    // phases are started in order, so they need to be ordered from highest to lowest threshold
+    phases.sort_by(|a, b| b.health_threshold.total_cmp(&a.health_threshold));
+    ty.phases = phases.leak();
+    let ty: &'static EnemyType = Box::leak(Box::new(ty));
+    interned[index] = Some(ty);
+    ty
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_shipped_enemy_data() {
+        let types = parse_enemy_types(include_str!("../data/enemies.txt"));
+        let get = |name: &str| {
+            *types
+                .iter()
+                .find(|f| f.name == name)
+                .unwrap_or_else(|| panic!("{name} missing"))
+        };
+        let horsey_rider = get("horsey_rider");
+        assert!(matches!(
+            horsey_rider.payload,
+            EnemyPayload::Some(payload, 1) if payload.name == "horsey"
+        ));
+        // fields of the base enemy are inherited, except payload_only
+        let blue_mushroom = get("blue_mushroom");
+        assert_eq!(blue_mushroom.speed, get("yellow_mushroom").speed);
+        assert!(!get("big_mushroom_guy").payload_only);
+        assert!(get("ultra_slime").boss);
+    }
+
+    #[test]
+    #[should_panic(expected = "unknown field")]
+    fn rejects_unknown_field() {
+        parse_enemy_types("[slime]\nslipperiness = 1.0");
+    }
+
+    #[test]
+    #[should_panic(expected = "expected 'field = value'")]
+    fn rejects_missing_equals() {
+        parse_enemy_types("[slime]\nspeed 1.0");
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid value")]
+    fn rejects_invalid_value() {
+        parse_enemy_types("[slime]\nmax_health = lots");
+    }
+
+    #[test]
+    #[should_panic(expected = "unknown base enemy")]
+    fn rejects_base_defined_below() {
+        parse_enemy_types("[big_slime : slime]\n[slime]");
+    }
+
+    #[test]
+    #[should_panic(expected = "unknown phase effect")]
+    fn rejects_unknown_phase_effect() {
+        parse_enemy_types("[slime]\nphase = 0.5 explode");
+    }
+
+    #[test]
+    #[should_panic(expected = "spawns itself")]
+    fn rejects_spawn_cycle() {
+        parse_enemy_types("[slime]\npayload = big_slime 1\n[big_slime]\npayload = slime 2");
+    }
+}
@@ -14,6 +14,8 @@ use crate::particle::Particle;
 use crate::particle::ParticleContext;
 use crate::rounds::*;
 use crate::save::*;
+use crate::spatial::*;
+use crate::status::*;
 use crate::tower::*;
 use crate::ui::*;
 use macroquad::rand;
@@ -27,6 +29,8 @@ mod map;
 mod particle;
 mod rounds;
 mod save;
+mod spatial;
+mod status;
 mod tower;
 mod ui;
 
@@ -34,13 +38,18 @@ fn get_seed() -> u64 {
     macroquad::miniquad::date::now() as u64
 }
 
-fn get_direction_nearest_enemy(enemies: &Vec<Enemy>, x: f32, y: f32) -> Option<Vec2> {
+fn get_direction_nearest_enemy(
+    enemies: &Vec<Enemy>,
+    x: f32,
+    y: f32,
+    detection: bool,
+) -> Option<Vec2> {
     if enemies.is_empty() {
         return None;
     }
     let mut nearest: Option<(f32, Vec2)> = None;
     for enemy in enemies {
-        if enemy.health <= 0.0 {
+        if enemy.health <= 0.0 || !enemy.is_visible(detection) {
             continue;
         }
         let (enemy_x, enemy_y) = enemy.get_centre();
@@ -51,6 +60,36 @@ fn get_direction_nearest_enemy(enemies: &Vec<Enemy>, x: f32, y: f32) -> Option<V
     }
     nearest.map(|f| f.1)
 }
+/// Creates enemies spread out around the score along the path, like the payload of a dying enemy
+fn spawn_along_path(
+    map: &Map,
+    ty: &'static EnemyType,
+    amount: u8,
+    state: EnemyState,
+) -> Vec<Enemy> {
+    let mut spawned = Vec::new();
+    for index in 0..amount {
+        let mut state = state;
+        state.score += index as f32 * 2.0 - amount as f32 + 1.0;
+        let pos = if ty.flying {
+            map.get_pos_along_flight(state.score)
+        } else {
+            map.get_pos_along_path(state.score)
+        };
+        let Some((x, y)) = pos else {
+            continue;
+        };
+        spawned.push(Enemy::new(ty, x * SPRITE_SIZE, y * SPRITE_SIZE, state));
+    }
+    spawned
+}
+/// Floating text showing gold earned from a kill
+struct GoldText {
+    x: f32,
+    y: f32,
+    amount: u16,
+    life: u8,
+}
 enum GameState {
     Running,
     Win,
@@ -64,10 +103,16 @@ struct Sludge<'a> {
     map: Map,
     map_index: usize,
     enemies: Vec<Enemy>,
+    /// Broad phase for projectile collisions, rebuilt every update
+    enemy_grid: SpatialGrid,
+    /// Buffers for collision queries, kept so they aren't reallocated for every projectile
+    hit_candidates: Vec<usize>,
+    hits: Vec<(f32, usize)>,
     towers: Vec<Tower>,
     projectiles: Vec<Projectile>,
     projectile_spawnlist: Vec<Projectile>,
     orphaned_particles: Vec<(Particle, ParticleContext)>,
+    gold_texts: Vec<GoldText>,
     lives: u8,
     ui_manager: UIManager<'a>,
     round_manager: RoundManager,
@@ -76,6 +121,8 @@ struct Sludge<'a> {
     rotating_tower: bool,
     moving: Option<Tower>,
     selected: Option<usize>,
+    /// Draw health bars above damaged enemies. Toggled with H
+    show_health_bars: bool,
     assets: &'a GameAssets,
 }
 impl<'a> Sludge<'a> {
@@ -96,10 +143,14 @@ impl<'a> Sludge<'a> {
             map,
             map_index,
             enemies: Vec::with_capacity(100),
+            enemy_grid: SpatialGrid::new(SCREEN_WIDTH, SCREEN_HEIGHT, ENEMY_GRID_CELL_SIZE),
+            hit_candidates: Vec::new(),
+            hits: Vec::new(),
             towers,
             projectiles: Vec::with_capacity(100),
             projectile_spawnlist: Vec::with_capacity(100),
             orphaned_particles: Vec::with_capacity(100),
+            gold_texts: Vec::new(),
             lives: STARTING_LIVES,
             round_manager,
             just_selected_tower: false,
@@ -107,6 +158,7 @@ impl<'a> Sludge<'a> {
             rotating_tower: false,
             moving: None,
             selected: None,
+            show_health_bars: true,
             ui_manager: UIManager::new(&assets.text_engine),
             assets,
         }
@@ -147,7 +199,7 @@ impl<'a> Sludge<'a> {
     fn start_round(&mut self) {
         self.projectiles.clear();
         if !self.lab {
-            self.ui_manager.shop = None;
+            self.ui_manager.close_shop();
         }
 
         self.round_manager.in_progress = true;
@@ -283,6 +335,10 @@ impl<'a> Sludge<'a> {
         }
     }
     fn handle_ui(&mut self, local_x: f32, local_y: f32) {
+        // show health of the first boss alive. drawn first, so the inventory and tooltips cover it
+        if let Some(boss) = self.enemies.iter().find(|f| f.ty.boss) {
+            self.draw_boss_health(boss);
+        }
         let selected_tower = self.selected.map(|index| &self.towers[index]);
         if let Some(tower) = selected_tower {
             self.assets
@@ -327,6 +383,17 @@ impl<'a> Sludge<'a> {
                     .draw_tile(tower.x, tower.y - 4.0, 34, false, 0.0);
             }
         }
+        // show info of hovered enemy, unless something is being dragged
+        if self.moving.is_none() && self.ui_manager.cursor_card.is_none() {
+            let hovered = self.enemies.iter().rev().find(|enemy| {
+                let (x, y) = enemy.get_centre();
+                let radius = SPRITE_SIZE * enemy.ty.size as f32 / 2.0;
+                ((x - local_x).powi(2) + (y - local_y).powi(2)).sqrt() < radius
+            });
+            if let Some(enemy) = hovered {
+                self.draw_enemy_info(local_x, local_y, enemy);
+            }
+        }
         let selected_tower = self.selected.map(|index| &mut self.towers[index]);
         self.ui_manager.handle_ui(
             local_x,
@@ -415,6 +482,115 @@ impl<'a> Sludge<'a> {
             self.pause();
         }
     }
+    fn draw_enemy_info(&self, mut local_x: f32, mut local_y: f32, enemy: &Enemy) {
+        let mut lines = vec![format!(
+            "health:{} of {}",
+            enemy.health.ceil(),
+            enemy.ty.max_health
+        )];
+        if enemy.ty.shield > 0.0 {
+            lines.push(format!(
+                "shield:{} of {}",
+                enemy.shield.ceil(),
+                enemy.ty.shield
+            ));
+        }
+        if enemy.ty.armour > 0.0 {
+            lines.push(format!("armour:{}", enemy.ty.armour));
+        }
+        // group damage types by how the enemy reacts to them
+        let mut immune = Vec::new();
+        let mut resists = Vec::new();
+        let mut weak = Vec::new();
+        for damage_type in [
+            DamageType::Magic,
+            DamageType::Pierce,
+            DamageType::Burn,
+            DamageType::Cold,
+            DamageType::Acid,
+        ] {
+            let name = format!("{damage_type:?}").to_lowercase();
+            match enemy.damage_multipliers.get(damage_type) {
+                0.0 => immune.push(name),
+                multiplier if multiplier < 1.0 => resists.push(name),
+                multiplier if multiplier > 1.0 => weak.push(name),
+                _ => {}
+            }
+        }
+        for (label, names) in [("immune", immune), ("resists", resists), ("weak to", weak)] {
+            if !names.is_empty() {
+                lines.push(format!("{label}:{}", names.join(" ")));
+            }
+        }
+        let statuses: Vec<String> = enemy
+            .statuses
+            .iter()
+            .filter(|status| status.frames > 0)
+            .map(|status| format!("{:?}", status.ty.kind).to_lowercase())
+            .collect();
+        if !statuses.is_empty() {
+            lines.push(format!("status:{}", statuses.join(" ")));
+        }
+        if let EnemyPayload::Some(enemy_type, amount) = enemy.ty.payload {
+            lines.push(format!(
+                "spawns:{amount} {}",
+                enemy_type.name.replace('_', " ")
+            ));
+        }
+
+        let name = enemy.ty.name.replace('_', " ");
+        // widen panel for long names and lines, 4 pixels per character
+        let longest = lines.iter().map(|f| f.len()).max().unwrap_or(0);
+        let width = ENEMY_INFO_WIDTH
+            .max(name.len() as f32 * 4.0 + SPRITE_SIZE + 6.0)
+            .max(longest as f32 * 4.0 + 4.0);
+        let height = CARD_SIZE + 2.0 + lines.len() as f32 * 5.0;
+        if local_x > SCREEN_WIDTH / 2.0 {
+            local_x -= width;
+        }
+        if local_y + height + 4.0 > SCREEN_HEIGHT {
+            local_y -= height;
+        }
+        draw_square(local_x, local_y, width, height);
+        self.assets
+            .icon_sheet
+            .draw_tile(local_x + 2.0, local_y + 2.0, enemy.ty.sprite, false, 0.0);
+        self.ui_manager
+            .text_engine
+            .draw_text(local_x + 4.0 + SPRITE_SIZE, local_y + 4.0, &name, 1);
+        for (index, line) in lines.iter().enumerate() {
+            self.ui_manager.text_engine.draw_text(
+                local_x + 2.0,
+                local_y + CARD_SIZE + index as f32 * 5.0,
+                line,
+                2,
+            );
+        }
+    }
+    fn draw_boss_health(&self, boss: &Enemy) {
+        let name = boss.ty.name.replace('_', " ");
+        let x = 64.0;
+        let width = SCREEN_WIDTH - SPRITE_SIZE - x;
+        draw_square(x, 0.0, width, 8.0);
+        self.ui_manager
+            .text_engine
+            .draw_text(x + 2.0, 2.0, &name, 0);
+
+        let bar_x = x + 4.0 * (name.len() as f32 + 1.0);
+        let bar_width = x + width - 2.0 - bar_x;
+        draw_rectangle(bar_x, 2.0, bar_width, 4.0, COLOR_BROWN);
+        let health = (boss.health / boss.ty.max_health).clamp(0.0, 1.0);
+        draw_rectangle(bar_x, 2.0, bar_width * health, 4.0, COLOR_RED);
+        // mark phases that haven't started yet
+        for phase in &boss.ty.phases[boss.phase..] {
+            let phase_x = (bar_x + bar_width * phase.health_threshold).floor();
+            draw_rectangle(phase_x, 2.0, 1.0, 4.0, COLOR_YELLOW);
+        }
+        if boss.shield > 0.0 {
+            let shield = (boss.shield / boss.ty.shield).clamp(0.0, 1.0);
+            draw_rectangle(bar_x, 5.0, bar_width * shield, 1.0, COLOR_CYAN);
+        }
+    }
     fn draw_tower(&self, tower: &Tower) {
         let mut sprite = tower.sprite;
         let mut flipped = false;
@@ -428,9 +604,27 @@ impl<'a> Sludge<'a> {
         } else if angle < 90.0 && angle > -90.0 {
             flipped = true;
         }
+        // grey out disabled towers, and flash scrambled ones
+        let color = if tower.disabled > 0.0 {
+            GRAY
+        } else if tower.scrambled > 0.0 && ((tower.scrambled * 10.0) as usize).is_multiple_of(2) {
+            COLOR_YELLOW
+        } else {
+            WHITE
+        };
         self.assets
             .icon_sheet
-            .draw_tile(tower.x, tower.y, sprite, flipped, 0.0);
+            .draw_tile_tinted(tower.x, tower.y, sprite, flipped, 0.0, color);
+        if tower.disabled > 0.0 {
+            let anim_frame = (tower.disabled * 10.0) as usize % 3;
+            self.assets.particle_sheet.draw_tile(
+                tower.x,
+                tower.y - SPRITE_SIZE / 2.0,
+                32 + 13 + anim_frame,
+                false,
+                0.0,
+            );
+        }
     }
     fn draw(&self) {
         self.assets.tileset.draw_tilemap(&self.map.background);
@@ -439,8 +633,8 @@ impl<'a> Sludge<'a> {
         for tower in self.towers.iter() {
             self.draw_tower(tower);
         }
-        for enemy in &self.enemies {
-            enemy.draw(self.assets)
+        for enemy in self.enemies.iter().filter(|f| !f.ty.flying) {
+            enemy.draw(self.assets, self.show_health_bars)
         }
         for projectile in self.projectiles.iter() {
             match &projectile.draw_type {
@@ -477,14 +671,36 @@ impl<'a> Sludge<'a> {
         for (particle, ctx) in self.orphaned_particles.iter() {
             (particle.function)(particle, ctx, &self.assets.particle_sheet);
         }
+        // flying enemies are drawn above everything else
+        for enemy in self.enemies.iter().filter(|f| f.ty.flying) {
+            enemy.draw(self.assets, self.show_health_bars)
+        }
+        for text in self.gold_texts.iter() {
+            let text_str = format!("+{}", text.amount);
+            // centre text, 4 pixels per character
+            let x = text.x - text_str.len() as f32 * 2.0;
+            self.ui_manager
+                .text_engine
+                .draw_text(x, text.y - 2.0, &text_str, 1);
+        }
     }
     fn update_particles(&mut self) {
         self.orphaned_particles.retain_mut(|(projectile, _)| {
             projectile.life += 1;
             projectile.life < projectile.lifetime
         });
+        self.gold_texts.retain_mut(|text| {
+            text.life += 1;
+            text.y -= 0.25;
+            text.life < GOLD_TEXT_LIFETIME
+        });
     }
     fn update_projectiles(&mut self) {
+        self.enemy_grid.clear();
+        for (index, enemy) in self.enemies.iter().enumerate() {
+            let (x, y) = enemy.get_centre();
+            self.enemy_grid.insert(index, Vec2::new(x, y));
+        }
         let death_queue = self.projectiles.extract_if(.., |projectile| {
             if projectile.modifier_data.confetti_trail {
                 self.orphaned_particles.push((
@@ -498,6 +714,7 @@ impl<'a> Sludge<'a> {
                     },
                 ));
             }
+            let old_pos = Vec2::new(projectile.x, projectile.y);
             projectile.x += projectile.direction.x * projectile.modifier_data.speed;
             projectile.y += projectile.direction.y * projectile.modifier_data.speed;
             projectile.life += 1.0;
@@ -513,12 +730,22 @@ impl<'a> Sludge<'a> {
             }
 
             if projectile.modifier_data.homing && !projectile.straight {
+                let detection = projectile.modifier_data.detection;
                 let dir = if projectile.modifier_data.smart_aim {
-                    self.enemies.last().map(|enemy| {
-                        Vec2::new(enemy.x - projectile.x, enemy.y - projectile.y).normalize()
-                    })
+                    self.enemies
+                        .iter()
+                        .rev()
+                        .find(|enemy| enemy.is_visible(detection))
+                        .map(|enemy| {
+                            Vec2::new(enemy.x - projectile.x, enemy.y - projectile.y).normalize()
+                        })
                 } else {
-                    get_direction_nearest_enemy(&self.enemies, projectile.x, projectile.y)
+                    get_direction_nearest_enemy(
+                        &self.enemies,
+                        projectile.x,
+                        projectile.y,
+                        detection,
+                    )
                 };
                 if let Some(dir) = dir {
                     projectile.direction = dir;
@@ -548,112 +775,179 @@ impl<'a> Sludge<'a> {
                 let angle = projectile.direction.to_angle();
                 projectile.direction = Vec2::from_angle(angle - old + amt);
             }
+            let new_pos = Vec2::new(projectile.x, projectile.y);
+            // find the first obstruction along the path travelled this frame
+            let wall_hit = if !projectile.modifier_data.ghost && projectile.ghost_frames == 0 {
+                self.map.cast_obstruction(old_pos, new_pos)
+            } else {
+                None
+            };
+            // enemies behind the obstruction can't be hit
+            let end_pos = wall_hit.map_or(new_pos, |(point, _)| point);
             if !projectile.modifier_data.anti_piercing {
-                // check if projectile hit any enemy
-                for enemy in self.enemies.iter_mut() {
+                // find enemies along the path travelled this frame, in the order they were reached
+                let radius = 8.0 + projectile.extra_size;
+                self.enemy_grid.query_segment(
+                    old_pos,
+                    end_pos,
+                    radius,
+                    |index| {
+                        let enemy = &self.enemies[index];
+                        let (enemy_x, enemy_y) = enemy.get_centre();
+                        (!(projectile.ground && enemy.ty.flying)
+                            && enemy.is_visible(projectile.modifier_data.detection))
+                        .then_some(Vec2::new(enemy_x, enemy_y))
+                    },
+                    &mut self.hit_candidates,
+                    &mut self.hits,
+                );
+                // piercing projectiles can crit several enemies in one frame, but only play the sound once
+                let mut crit_sound = ProjectileSound::Crit;
+                for &(t, index) in self.hits.iter() {
+                    let enemy = &mut self.enemies[index];
                     // check that enemy hasnt already been killed this frame
                     if enemy.health <= 0.0 {
                         continue;
                     }
-                    let (enemy_x, enemy_y) = enemy.get_centre();
-
-                    let distance = ((enemy_x - projectile.x).powi(2)
-                        + (enemy_y - projectile.y).powi(2))
-                    .sqrt();
-                    if distance < 8.0 + projectile.extra_size {
-                        // hit!
-                        let mut damage = projectile.modifier_data.damage.clone();
-                        // if projectile deals random damage, apply that
-                        if let Some((min, max)) = projectile.random_damage {
-                            let amount = rand::gen_range(min, max) as f32;
-
-                            if let Some(amt) = damage.get_mut(&DamageType::Magic) {
-                                *amt += amount;
-                            } else {
-                                damage.insert(DamageType::Magic, amount);
-                            }
-                        }
-                        for (damage_type, mut amount) in damage {
-                            match &enemy.ty.damage_resistance {
-                                // skip damage of enemy if fully resistant
-                                DamageResistance::Full(ty) => {
-                                    if *ty == damage_type {
-                                        continue;
-                                    }
-                                }
-                                // halve damage if enemy partially resistant
-                                DamageResistance::Partial(ty) => {
-                                    if *ty == damage_type {
-                                        amount /= 2.0;
-                                    }
-                                }
-                                DamageResistance::None => {}
-                            }
-                            enemy.health -= amount;
-                        }
-                        if !projectile.modifier_data.damage.is_empty() {
-                            enemy.gold_factor = projectile.modifier_data.gold_factor;
+                    // move projectile to where it hit, so particles and payloads spawn there
+                    let hit_pos = old_pos.lerp(end_pos, t);
+                    projectile.x = hit_pos.x;
+                    projectile.y = hit_pos.y;
+                    // skip enemies already hit, unless the re-hit cooldown has passed
+                    let rehit_frames = projectile.modifier_data.rehit_frames as f32;
+                    if let Some(hit) = projectile
+                        .hit_enemies
+                        .iter_mut()
+                        .find(|(id, _)| *id == enemy.id)
+                    {
+                        if rehit_frames == 0.0 || projectile.life - hit.1 < rehit_frames {
+                            continue;
                         }
-                        // play sound
-                        projectile.hit_sound.play(&self.assets.sfx_manager);
-                        projectile.hit_sound = ProjectileSound::None;
+                        hit.1 = projectile.life;
+                    } else {
+                        projectile.hit_enemies.push((enemy.id, projectile.life));
+                    }
+                    // hit!
+                    let mut damage = projectile.modifier_data.damage.clone();
+                    // if projectile deals random damage, apply that
+                    if let Some((min, max)) = projectile.random_damage {
+                        let amount = rand::gen_range(min, max) as f32;
 
-                        // also check whether enemy should be frozen
-                        // if projectile deals cold damage
-                        if projectile
-                            .modifier_data
-                            .damage
-                            .get(&DamageType::Cold)
-                            .is_some_and(|f| *f > 0.0)
-                        {
-                            let is_cold_resistant = match enemy.ty.damage_resistance {
-                                DamageResistance::None => false,
-                                DamageResistance::Full(ty) => matches!(ty, DamageType::Cold),
-                                DamageResistance::Partial(ty) => matches!(ty, DamageType::Cold),
-                            };
-                            // if enemy isnt cold resistant
-                            if !is_cold_resistant {
-                                enemy.state.freeze_frames = FREEZE_TIME;
-                            }
+                        if let Some(amt) = damage.get_mut(&DamageType::Magic) {
+                            *amt += amount;
+                        } else {
+                            damage.insert(DamageType::Magic, amount);
                         }
-                        // poison enemy if projectile has poison frames
-                        if projectile.modifier_data.poison > 0 {
-                            enemy.poison_frames = enemy
-                                .poison_frames
-                                .saturating_add(projectile.modifier_data.poison)
-                                .min(30);
+                    }
+                    // roll for critical hit. this is done before the hit applies any statuses,
+                    // so conditional crits only count statuses the enemy already had
+                    let modifier_data = &projectile.modifier_data;
+                    let crit = rand::gen_range(0.0, 1.0) < modifier_data.crit_chance
+                        || (modifier_data.crit_stunned
+                            && enemy.get_status(StatusKind::Stun).is_some())
+                        || (modifier_data.crit_frozen
+                            && enemy.get_status(StatusKind::Freeze).is_some());
+                    if crit {
+                        for amount in damage.values_mut() {
+                            *amount *= CRIT_MULTIPLIER + modifier_data.crit_multiplier;
+                        }
+                        crit_sound.play(&self.assets.sfx_manager);
+                        crit_sound = ProjectileSound::None;
+                    }
+                    // freeze enemy if projectile deals cold damage.
+                    // this is done before damage is dealt, such that cold and pierce damage in the same hit shatters the enemy
+                    if damage.get(&DamageType::Cold).is_some_and(|f| *f > 0.0) {
+                        enemy.apply_status(&FREEZE, FREEZE_TIME);
+                    }
+                    // apply elemental reactions between damage types and the enemy's statuses
+                    let mut extra_payload = false;
+                    for reaction in REACTIONS {
+                        if enemy.get_status(reaction.status).is_none() {
+                            continue;
                         }
-                        // stun enemy if projectile has stun frames
-                        if projectile.modifier_data.stuns > 0 && enemy.stun_immunity_frames == 0 {
-                            enemy.stun_frames = enemy
-                                .stun_frames
-                                .saturating_add(projectile.modifier_data.stuns)
-                                .min(35);
-                            enemy.stun_immunity_frames = STUN_IMMUNITY_FRAMES;
+                        let Some(amount) = damage
+                            .get_mut(&reaction.damage_type)
+                            .filter(|amount| **amount > 0.0)
+                        else {
+                            continue;
+                        };
+                        *amount *= reaction.damage_factor;
+                        extra_payload |= reaction.extra_payload;
+                        if reaction.consumes_status {
+                            enemy.remove_status(reaction.status);
                         }
+                    }
+                    for (damage_type, amount) in damage {
+                        enemy.take_hit(damage_type, amount);
+                    }
+                    if !projectile.modifier_data.damage.is_empty() {
+                        enemy.gold_factor = projectile.modifier_data.gold_factor;
+                    }
+                    // play sound
+                    projectile.hit_sound.play(&self.assets.sfx_manager);
+                    projectile.hit_sound = ProjectileSound::None;
 
-                        // send trigger payload
-                        if !projectile.payload.is_empty() {
+                    // ignite enemy if projectile deals burn damage
+                    if projectile
+                        .modifier_data
+                        .damage
+                        .get(&DamageType::Burn)
+                        .is_some_and(|f| *f > 0.0)
+                    {
+                        enemy.apply_status(&BURNING, BURN_TIME);
+                    }
+                    // poison enemy if projectile has poison frames
+                    if projectile.modifier_data.poison > 0 {
+                        enemy.apply_status(&POISON, projectile.modifier_data.poison);
+                    }
+                    // stun enemy if projectile has stun frames
+                    if projectile.modifier_data.stuns > 0 {
+                        enemy.apply_status(&STUN, projectile.modifier_data.stuns);
+                    }
+                    // push enemy back along the path
+                    if projectile.modifier_data.knockback > 0.0 && enemy.apply_status(&KNOCKBACK, 1)
+                    {
+                        enemy.state.score = (enemy.state.score
+                            - projectile.modifier_data.knockback / enemy.ty.size as f32)
+                            .max(0.0);
+                    }
+
+                    // send trigger payload
+                    if !projectile.payload.is_empty() {
+                        // marked before firing, so neither copy of the payload can release another one
+                        let extra_payload = extra_payload && !projectile.extra_payload_released;
+                        projectile.extra_payload_released |= extra_payload;
+                        self.projectile_spawnlist
+                            .append(&mut projectile.fire_payload());
+                        if extra_payload {
                             self.projectile_spawnlist
                                 .append(&mut projectile.fire_payload());
                         }
-                        // spawn hitmarker particle
-                        self.orphaned_particles.push((
-                            particle::HIT_MARKER,
-                            ParticleContext {
-                                x: projectile.x,
-                                y: projectile.y,
-                                origin_x: projectile.spawn_x,
-                                origin_y: projectile.spawn_y,
-                                direction: projectile.direction,
-                            },
-                        ));
-                        if !projectile.modifier_data.piercing {
-                            // kil projectile if not piercing
-                            return true;
-                        }
+                    }
+                    // spawn hitmarker particle
+                    self.orphaned_particles.push((
+                        if crit {
+                            particle::CRIT_MARKER
+                        } else {
+                            particle::HIT_MARKER
+                        },
+                        ParticleContext {
+                            x: projectile.x,
+                            y: projectile.y,
+                            origin_x: projectile.spawn_x,
+                            origin_y: projectile.spawn_y,
+                            direction: projectile.direction,
+                        },
+                    ));
+                    match projectile.modifier_data.pierce {
+                        // kil projectile if out of pierce
+                        0 => return true,
+                        INFINITE_PIERCE => {}
+                        _ => projectile.modifier_data.pierce -= 1,
                     }
                 }
+                projectile.x = new_pos.x;
+                projectile.y = new_pos.y;
             }
 
             // lifetime of -1.0 means projectile just doesnt despawn
@@ -664,41 +958,38 @@ impl<'a> Sludge<'a> {
                 }
             }
             // check for collisions
-            if !projectile.modifier_data.ghost
-                && projectile.ghost_frames == 0
-                && projectile.x > 0.0
-                && projectile.y > 0.0
-            {
-                let (x, y) = (
-                    projectile.x as usize / SPRITE_SIZE_USIZE,
-                    projectile.y as usize / SPRITE_SIZE_USIZE,
-                );
-                if y < self.map.obstructions.len()
-                    && x < self.map.obstructions[0].len()
-                    && self.map.obstructions[y][x] != 0
-                {
-                    // send trigger payload
-                    if !projectile.payload.is_empty() && !projectile.only_enemy_triggers {
-                        let mut spawnlist = projectile.fire_payload();
-                        for p in spawnlist.iter_mut() {
-                            let max_spread = (p.modifier_data.spread + DEFAULT_SPREAD).max(0.0);
-                            let spread = rand::gen_range(-max_spread, max_spread);
-                            // flip their direction, so payload is shot off like a bounce off the obstacle
-                            let inverted =
-                                Vec2::from_angle(PI + projectile.direction.to_angle() + spread);
-                            p.direction = inverted;
-                            p.ghost_frames = 10;
-                        }
-                        self.projectile_spawnlist.append(&mut spawnlist);
+            if let Some((point, normal)) = wall_hit {
+                // move projectile to just outside the obstruction
+                projectile.x = point.x + normal.x * 0.5;
+                projectile.y = point.y + normal.y * 0.5;
+                // reflect direction off the face of the obstruction
+                let reflected =
+                    projectile.direction - 2.0 * projectile.direction.dot(normal) * normal;
+                // send trigger payload
+                if !projectile.payload.is_empty() && !projectile.only_enemy_triggers {
+                    let mut spawnlist = projectile.fire_payload();
+                    for p in spawnlist.iter_mut() {
+                        let max_spread = (p.modifier_data.spread + DEFAULT_SPREAD).max(0.0);
+                        let spread = rand::gen_range(-max_spread, max_spread);
+                        // payload is shot off like a bounce off the obstacle
+                        p.direction = Vec2::from_angle(reflected.to_angle() + spread);
+                        p.ghost_frames = 10;
                     }
+                    self.projectile_spawnlist.append(&mut spawnlist);
+                }
+                if projectile.modifier_data.bounces == 0 {
                     return true;
                 }
+                projectile.modifier_data.bounces -= 1;
+                projectile.direction = reflected;
             }
             false
         });
         for killed in death_queue.collect::<Vec<Projectile>>() {
             if !killed.death_payload.is_empty() {
                 let mut context = FiringContext::default();
+                context.modifier_data.gold_factor = killed.modifier_data.gold_factor;
+                context.modifier_data.detection = killed.modifier_data.detection;
                 fire_deck(
                     killed.x,
                     killed.y,
@@ -731,12 +1022,22 @@ impl<'a> Sludge<'a> {
         // and play sfx
         for projectile in &mut self.projectile_spawnlist {
             if projectile.modifier_data.aim {
+                let detection = projectile.modifier_data.detection;
                 let dir = if projectile.modifier_data.smart_aim {
-                    self.enemies.last().map(|enemy| {
-                        Vec2::new(enemy.x - projectile.x, enemy.y - projectile.y).normalize()
-                    })
+                    self.enemies
+                        .iter()
+                        .rev()
+                        .find(|enemy| enemy.is_visible(detection))
+                        .map(|enemy| {
+                            Vec2::new(enemy.x - projectile.x, enemy.y - projectile.y).normalize()
+                        })
                 } else {
-                    get_direction_nearest_enemy(&self.enemies, projectile.x, projectile.y)
+                    get_direction_nearest_enemy(
+                        &self.enemies,
+                        projectile.x,
+                        projectile.y,
+                        detection,
+                    )
                 };
                 if let Some(direction_nearest) = dir {
                     let max_spread = projectile.modifier_data.spread.max(0.0);
@@ -750,9 +1051,13 @@ impl<'a> Sludge<'a> {
         self.projectiles.append(&mut self.projectile_spawnlist);
     }
     fn update_towers(&mut self, deltatime_ms: u128) {
+        let deltatime = deltatime_ms as f32 / 1000.0;
         for tower in self.towers.iter_mut() {
-            if !tower.can_shoot() {
-                tower.delay_counter -= deltatime_ms as f32 / 1000.0;
+            tower.scrambled = (tower.scrambled - deltatime).max(0.0);
+            if tower.disabled > 0.0 {
+                tower.disabled -= deltatime;
+            } else if !tower.can_shoot() {
+                tower.delay_counter -= deltatime;
             } else {
                 let mut spawn_queue = tower.shoot();
                 self.projectile_spawnlist.append(&mut spawn_queue);
@@ -781,42 +1086,142 @@ impl<'a> Sludge<'a> {
             self.spawn_enemy(enemy);
         }
 
-        self.enemies.retain_mut(|enemy| {
-            if enemy.poison_frames > 0 {
-                let mut damage = POISON_DAMAGE;
-                match &enemy.ty.damage_resistance {
-                    DamageResistance::None => {}
-                    DamageResistance::Full(ty) => {
-                        if *ty == DamageType::Acid {
-                            damage = 0.0;
-                        }
+        // use abilities of enemies that are off cooldown
+        let mut abilities = Vec::new();
+        for enemy in self.enemies.iter_mut() {
+            if let Some(ability) = enemy.update_ability() {
+                abilities.push((ability, enemy.get_centre(), enemy.state));
+            }
+        }
+        for (ability, (x, y), state) in abilities {
+            // enemies killed this frame are only removed later, and mustn't be healed back to life
+            let in_range = |enemy: &Enemy, range: f32| {
+                let (enemy_x, enemy_y) = enemy.get_centre();
+                enemy.health > 0.0 && ((enemy_x - x).powi(2) + (enemy_y - y).powi(2)).sqrt() < range
+            };
+            let tower_in_range = |tower: &Tower, range: f32| {
+                let tower_x = tower.x + SPRITE_SIZE / 2.0;
+                let tower_y = tower.y + SPRITE_SIZE / 2.0;
+                ((tower_x - x).powi(2) + (tower_y - y).powi(2)).sqrt() < range
+            };
+            match ability {
+                EnemyAbility::None => {}
+                EnemyAbility::Heal(amount, range) => {
+                    for enemy in self.enemies.iter_mut().filter(|f| in_range(f, range)) {
+                        enemy.health = (enemy.health + amount).min(enemy.ty.max_health);
                     }
-                    DamageResistance::Partial(ty) => {
-                        if *ty == DamageType::Acid {
-                            damage /= 2.0;
-                        }
+                }
+                EnemyAbility::Summon(enemy_type, amount) => {
+                    spawnlist.append(&mut spawn_along_path(&self.map, enemy_type, amount, state));
+                }
+                EnemyAbility::SpeedAura(range) => {
+                    for enemy in self.enemies.iter_mut().filter(|f| in_range(f, range)) {
+                        enemy.apply_status(&HASTE, HASTE_TIME);
+                    }
+                }
+                EnemyAbility::Cleanse(range) => {
+                    for enemy in self.enemies.iter_mut().filter(|f| in_range(f, range)) {
+                        enemy.remove_status(StatusKind::Poison);
+                        enemy.remove_status(StatusKind::Freeze);
+                    }
+                }
+                EnemyAbility::Disable(range, seconds) => {
+                    for tower in self.towers.iter_mut().filter(|f| tower_in_range(f, range)) {
+                        tower.disable(seconds);
                     }
                 }
-                enemy.health -= damage;
+                EnemyAbility::Scramble(range) => {
+                    for tower in self.towers.iter_mut().filter(|f| tower_in_range(f, range)) {
+                        tower.scramble();
+                    }
+                }
+            }
+        }
+
+        // find enemies burning enough to spread fire to their neighbours
+        let burning: Vec<(f32, f32)> = self
+            .enemies
+            .iter()
+            .filter(|enemy| {
+                enemy
+                    .get_status(StatusKind::Burning)
+                    .is_some_and(|status| status.frames > BURN_SPREAD_FRAMES)
+            })
+            .map(|enemy| enemy.get_centre())
+            .collect();
+
+        // towers with detection reveal camouflaged enemies around them
+        let detectors: Vec<(f32, f32)> = self
+            .towers
+            .iter()
+            .filter(|tower| tower.has_detection())
+            .map(|tower| (tower.x + SPRITE_SIZE / 2.0, tower.y + SPRITE_SIZE / 2.0))
+            .collect();
+
+        self.enemies.retain_mut(|enemy| {
+            if enemy.ty.camouflage {
+                let (x, y) = enemy.get_centre();
+                enemy.detected = detectors.iter().any(|(tower_x, tower_y)| {
+                    ((tower_x - x).powi(2) + (tower_y - y).powi(2)).sqrt() < DETECTION_RANGE
+                });
+            }
+            if !burning.is_empty()
+                && enemy.get_status(StatusKind::Burning).is_none()
+                && rand::gen_range(0, 100) < BURN_SPREAD_CHANCE
+            {
+                let (x, y) = enemy.get_centre();
+                if burning.iter().any(|(burning_x, burning_y)| {
+                    ((burning_x - x).powi(2) + (burning_y - y).powi(2)).sqrt()
+                        < BURN_SPREAD_DISTANCE
+                }) {
+                    enemy.apply_status(&BURNING, BURN_SPREAD_FRAMES);
+                }
+            }
+            enemy.update_shield();
+            let speed_factor = enemy.tick_statuses();
+            if enemy.health > 0.0 {
+                for (enemy_type, amount) in enemy.update_phases() {
+                    spawnlist.append(&mut spawn_along_path(
+                        &self.map,
+                        enemy_type,
+                        amount,
+                        enemy.state,
+                    ));
+                }
             }
             if enemy.health <= 0.0 {
-                self.ui_manager.gold +=
-                    (enemy.ty.damage as f32 * 4.0 * enemy.gold_factor.unwrap_or(1.0)) as u16;
+                let gold = (enemy.ty.bounty as f32
+                    * enemy.gold_factor.unwrap_or(1.0)
+                    * self.map.gold_multiplier) as u16;
+                self.ui_manager.gold += gold;
+                if gold > 0 {
+                    let (x, y) = enemy.get_centre();
+                    self.gold_texts.push(GoldText {
+                        x,
+                        y,
+                        amount: gold,
+                        life: 0,
+                    });
+                }
                 if let EnemyPayload::Some(enemy_type, amount) = enemy.ty.payload {
-                    for index in 0..amount {
-                        let score = enemy.state.score + index as f32 * 2.0 - amount as f32 + 1.0;
-                        let mut state = enemy.state;
-                        state.score = score;
-                        let Some((x, y)) = self.map.get_pos_along_path(score) else {
-                            continue;
-                        };
-                        let new = Enemy::new(enemy_type, x * SPRITE_SIZE, y * SPRITE_SIZE, state);
+                    for mut new in spawn_along_path(&self.map, enemy_type, amount, enemy.state) {
+                        new.statuses = enemy
+                            .statuses
+                            .iter()
+                            .filter(|status| status.ty.inherited)
+                            .copied()
+                            .collect();
                         spawnlist.push(new);
                     }
                 }
                 return false;
             }
-            if let Some((x, y)) = self.map.get_pos_along_path(enemy.state.score) {
+            let pos = if enemy.ty.flying {
+                self.map.get_pos_along_flight(enemy.state.score)
+            } else {
+                self.map.get_pos_along_path(enemy.state.score)
+            };
+            if let Some((x, y)) = pos {
                 if x * SPRITE_SIZE > enemy.x {
                     enemy.moving_left = false;
                 } else if x * SPRITE_SIZE < enemy.x {
@@ -825,29 +1230,14 @@ impl<'a> Sludge<'a> {
                 enemy.x = x * SPRITE_SIZE;
                 enemy.y = y * SPRITE_SIZE;
             } else {
-                self.lives = self.lives.saturating_sub(enemy.ty.calc_damage());
+                self.lives = self.lives.saturating_sub(enemy.ty.calc_leak_damage());
                 return false;
             }
-            let mut speed_factor = 1.0;
-            if enemy.state.freeze_frames > 0 {
-                enemy.state.freeze_frames -= 1;
-                speed_factor = 0.55;
-            }
-
-            if enemy.stun_frames > 0 {
-                enemy.stun_frames -= 1;
-                // make larger enemies not stun entirely
-                if enemy.ty.size == 1 {
-                    speed_factor = 0.0
-                } else {
-                    speed_factor = 0.4
-                }
-            }
-            // only subtract stun immunity frames after stun frames have wore off
-            else if enemy.stun_immunity_frames > 0 {
-                enemy.stun_immunity_frames -= 1;
+            let mut speed = enemy.ty.speed * enemy.phase_speed * speed_factor;
+            if enemy.ty.flying {
+                speed *= self.map.get_flight_speed_factor();
             }
-            enemy.state.score += enemy.ty.speed * speed_factor;
+            enemy.state.score += speed;
             true
         });
 
@@ -921,15 +1311,17 @@ struct GameAssets {
 }
 
 fn create_random_enemy(index: usize) -> Enemy {
-    let ty = &ENEMY_TYPES[rand::gen_range(0, ENEMY_TYPES.len())];
+    let types: Vec<&'static EnemyType> = enemy_types()
+        .iter()
+        .filter(|ty| !ty.payload_only)
+        .copied()
+        .collect();
+    let ty = types[rand::gen_range(0, types.len())];
     Enemy::new(
         ty,
         index as f32 * -SPRITE_SIZE * 2.0 - SPRITE_SIZE * ty.size as f32,
         SCREEN_HEIGHT - SPRITE_SIZE,
-        EnemyState {
-            score: 0.0,
-            freeze_frames: 0,
-        },
+        EnemyState { score: 0.0 },
     )
 }
 struct GameManager<'a> {
@@ -1076,7 +1468,7 @@ impl<'a> GameManager<'a> {
 
         self.menu_enemies.sort_by(|a, b| a.x.total_cmp(&b.x));
         let extracted = self.menu_enemies.extract_if(.., |enemy| {
-            enemy.draw(self.assets);
+            enemy.draw(self.assets, false);
             enemy.x += enemy.ty.speed * 0.5;
             enemy.state.score += enemy.ty.speed * 0.5;
             enemy.x > SCREEN_WIDTH
@@ -1189,6 +1581,9 @@ impl<'a> GameManager<'a> {
         if is_key_pressed(KeyCode::Q) || is_key_pressed(KeyCode::Escape) {
             game.pause();
         }
+        if is_key_pressed(KeyCode::H) {
+            game.show_health_bars = !game.show_health_bars;
+        }
 
         match game.state {
             GameState::Running => {
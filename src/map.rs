@@ -23,6 +23,17 @@ impl Spritesheet {
         (x, y)
     }
     pub fn draw_tile(&self, x: f32, y: f32, id: usize, flipped: bool, rotation: f32) {
+        self.draw_tile_tinted(x, y, id, flipped, rotation, WHITE);
+    }
+    pub fn draw_tile_tinted(
+        &self,
+        x: f32,
+        y: f32,
+        id: usize,
+        flipped: bool,
+        rotation: f32,
+        color: Color,
+    ) {
         let (texture_x, texture_y) = self.id_to_pos(id);
         let size = self.sprite_size as f32;
         let params = DrawTextureParams {
@@ -38,7 +49,7 @@ impl Spritesheet {
             flip_y: false,
             pivot: None,
         };
-        draw_texture_ex(&self.texture, x, y, WHITE, params);
+        draw_texture_ex(&self.texture, x, y, color, params);
     }
     pub fn draw_tilemap(&self, map: &TileMap) {
         for (y, row) in map.iter().enumerate() {
@@ -74,6 +85,8 @@ pub struct Map {
     pub out_of_bounds: TileMap,
     pub points: Vec<(f32, f32)>,
     pub tower_spawnpoints: [(usize, usize); 4],
+    /// Factor gold earned from kills is multiplied by
+    pub gold_multiplier: f32,
 }
 impl Map {
     pub fn draw_preview(&self, x: f32, y: f32, old_camera: &Camera2D, tileset: &Spritesheet) {
@@ -119,6 +132,100 @@ impl Map {
         }
         true
     }
+    /// Is the position (in pixels) inside an obstruction tile
+    pub fn is_obstruction(&self, x: f32, y: f32) -> bool {
+        self.is_obstruction_tile((Vec2::new(x, y) / SPRITE_SIZE).floor())
+    }
+    fn is_obstruction_tile(&self, tile: Vec2) -> bool {
+        if tile.x < 0.0 || tile.y < 0.0 {
+            return false;
+        }
+        let (x, y) = (tile.x as usize, tile.y as usize);
+        y < self.obstructions.len()
+            && x < self.obstructions[0].len()
+            && self.obstructions[y][x] != 0
+    }
+    /// Finds the first obstruction tile crossed when moving from `from` to `to`, by stepping through
+    /// every tile along the way. Returns the point the obstruction was entered at, and the normal of
+    /// the tile face it was entered through.
+    pub fn cast_obstruction(&self, from: Vec2, to: Vec2) -> Option<(Vec2, Vec2)> {
+        // the traversal would never reach the end tile
+        if !from.is_finite() || !to.is_finite() {
+            return None;
+        }
+        let delta = to - from;
+        // started inside the obstruction, so just send it back the way it came
+        if self.is_obstruction(from.x, from.y) {
+            return Some((from, -delta.normalize_or_zero()));
+        }
+        let mut tile = (from / SPRITE_SIZE).floor();
+        let end_tile = (to / SPRITE_SIZE).floor();
+        let step = Vec2::new(delta.x.signum(), delta.y.signum());
+        // how far along the segment (0.0 - 1.0) to move to cross a whole tile on each axis
+        let t_delta = Vec2::new((SPRITE_SIZE / delta.x).abs(), (SPRITE_SIZE / delta.y).abs());
+        // how far along the segment the next tile edge is on each axis
+        let next_edge = (tile + step.max(Vec2::ZERO)) * SPRITE_SIZE;
+        let mut t_max = Vec2::new(
+            if delta.x != 0.0 {
+                (next_edge.x - from.x) / delta.x
+            } else {
+                f32::INFINITY
+            },
+            if delta.y != 0.0 {
+                (next_edge.y - from.y) / delta.y
+            } else {
+                f32::INFINITY
+            },
+        );
+        // each step moves one tile closer to the end tile, so more steps than this can't be needed
+        let max_steps = ((end_tile - tile).abs().element_sum()) as usize;
+        for _ in 0..max_steps {
+            let (t, normal) = if t_max.x < t_max.y {
+                tile.x += step.x;
+                t_max.x += t_delta.x;
+                (t_max.x - t_delta.x, Vec2::new(-step.x, 0.0))
+            } else {
+                tile.y += step.y;
+                t_max.y += t_delta.y;
+                (t_max.y - t_delta.y, Vec2::new(0.0, -step.y))
+            };
+            if t > 1.0 {
+                break;
+            }
+            if self.is_obstruction_tile(tile) {
+                return Some((from + delta * t, normal));
+            }
+        }
+        None
+    }
+    /// Like [Map::get_pos_along_path], but in a straight line from the start to the end of the path, used by flying enemies.
+    /// Score is still the distance along the path, so flying and walking enemies can be compared by how far they've gotten.
+    pub fn get_pos_along_flight(&self, score: f32) -> Option<(f32, f32)> {
+        let progress = score / self.get_path_length();
+        if progress >= 1.0 {
+            return None;
+        }
+        let start = self.points[0];
+        let end = self.points[self.points.len() - 1];
+        Some((
+            start.0 + (end.0 - start.0) * progress,
+            start.1 + (end.1 - start.1) * progress,
+        ))
+    }
+    /// Factor the score of flying enemies increases by, such that they actually fly at their speed
+    pub fn get_flight_speed_factor(&self) -> f32 {
+        let start = self.points[0];
+        let end = self.points[self.points.len() - 1];
+        let distance = ((end.0 - start.0).powi(2) + (end.1 - start.1).powi(2)).sqrt() * SPRITE_SIZE;
+        // paths that loop back to where they started have no straight line to fly along
+        if distance <= 0.0 {
+            return 1.0;
+        }
+        self.get_path_length() / distance
+    }
+    fn get_path_length(&self) -> f32 {
+        (self.points.len() - 1) as f32 * SPRITE_SIZE
+    }
     pub fn get_pos_along_path(&self, score: f32) -> Option<(f32, f32)> {
         let tiled = score / SPRITE_SIZE;
         let lower = (score / SPRITE_SIZE).floor();
@@ -242,6 +349,16 @@ pub fn parse_points_from_tilemap(map: &TileMap) -> Vec<(f32, f32)> {
     }
 }
 
+/// Finds the value of a custom map property, if it is set
+pub fn parse_map_property<'a>(xml: &'a str, property_name: &str) -> Option<&'a str> {
+    let pattern = format!("<property name=\"{property_name}\" ");
+    xml.split_once(&pattern)?
+        .1
+        .split_once("value=\"")?
+        .1
+        .split_once('"')
+        .map(|f| f.0)
+}
 pub fn parse_tilemap_layer(xml: &str, layer_name: &str) -> Result<TileMap, BadMapDataError> {
     let pattern = format!("name=\"{layer_name}\" ");
     let xml = xml
@@ -212,6 +212,32 @@ pub const HIT_MARKER: Particle = Particle {
     },
 };
 
+pub const CRIT_MARKER: Particle = Particle {
+    life: 0,
+    lifetime: 6,
+    function: &|this, ctx, particles| {
+        particles.draw_tile(
+            ctx.x - SPRITE_SIZE / 2.0,
+            ctx.y - SPRITE_SIZE / 2.0,
+            6,
+            false,
+            0.0,
+        );
+        // yellow sparks flying outwards diagonally
+        let dist = 3.0 + this.life as f32;
+        for i in 0..4 {
+            let offset = Vec2::from_angle(PI / 4.0 + i as f32 * PI / 2.0) * dist;
+            draw_rectangle(
+                ctx.x + offset.x - 0.5,
+                ctx.y + offset.y - 0.5,
+                1.0,
+                1.0,
+                YELLOW,
+            );
+        }
+    },
+};
+
 pub const BUBBLE: Particle = Particle {
     life: 0,
     lifetime: 19,
@@ -307,6 +333,27 @@ pub const FIREBALL: Particle = Particle {
     },
 };
 
+pub const FIRE_PATCH: Particle = Particle {
+    life: 0,
+    lifetime: 10,
+    function: &|this, ctx, particles| {
+        // a couple of small flames pointing upwards
+        for (index, (offset_x, offset_y)) in [(-4.0, -2.0), (2.0, -4.0), (-2.0, 3.0), (4.0, 2.0)]
+            .into_iter()
+            .enumerate()
+        {
+            let anim_frame_offset = (this.life as usize / 3 + index) % 3;
+            particles.draw_tile(
+                ctx.x + offset_x - SPRITE_SIZE / 2.0,
+                ctx.y + offset_y - SPRITE_SIZE / 2.0,
+                38 + anim_frame_offset,
+                false,
+                -PI / 2.0,
+            );
+        }
+    },
+};
+
 pub const ACID_PUDDLE: Particle = Particle {
     life: 0,
     lifetime: 10,
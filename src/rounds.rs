@@ -3,11 +3,14 @@ use macroquad::rand;
 use crate::{
     assets::SublevelHashmap,
     consts::*,
-    enemy::{EnemyType, ENEMY_TYPES},
+    enemy::{enemy_types, EnemyType},
 };
 
 fn get_index_of_enemy(name: &str) -> usize {
-    ENEMY_TYPES.iter().position(|f| f.name == name).unwrap()
+    enemy_types()
+        .iter()
+        .position(|f| f.name == name)
+        .unwrap_or_else(|| panic!("unknown enemy {name} in round data!"))
 }
 
 pub fn decode_rounds(data: &str, mut sublevels: Option<SublevelHashmap>) -> Vec<Round> {
@@ -91,7 +94,7 @@ impl RoundManager {
                 }
                 RoundEntry::Spawn(target, amount) => {
                     if counter < *amount {
-                        return RoundUpdate::Spawn(&ENEMY_TYPES[*target]);
+                        return RoundUpdate::Spawn(enemy_types()[*target]);
                     } else {
                         counter -= amount;
                     }
@@ -48,7 +48,7 @@ pub struct TowerSaveData {
     x: f32,
     y: f32,
     direction: f32,
-    slots: [Option<u8>; TOWER_MAX_SLOTS],
+    slots: [Option<VirtualCard>; TOWER_MAX_SLOTS],
 }
 #[derive(Debug, PartialEq, Clone, Decode, Encode)]
 pub struct SaveData {
@@ -57,42 +57,53 @@ pub struct SaveData {
     pub gold: u16,
     pub round_index: u8,
     pub map_index: u8,
-    pub shop_items:
-        [Option<(u8, u16)>; DEFAULT_SHOP_SLOTS_HORIZONTAL * DEFAULT_SHOP_SLOTS_VERTICAL],
+    /// Card, price and whether slot is locked
+    pub shop_items: [Option<(VirtualCard, u16, bool)>;
+        DEFAULT_SHOP_SLOTS_HORIZONTAL * DEFAULT_SHOP_SLOTS_VERTICAL],
     pub towers: [Option<TowerSaveData>; 4],
-    pub inventory: [Option<u8>; INV_SLOTS_HORIZONTAL * INV_SLOTS_VERTICAL],
+    pub inventory: [Option<VirtualCard>; INV_SLOTS_HORIZONTAL * INV_SLOTS_VERTICAL],
 }
-fn actualize_virtual_card(mut card: u8, cards: &[Card]) -> Card {
+/// A card as it is stored in save data. Index of card (offset if trigger), and the card's level
+type VirtualCard = (u8, u8);
+
+fn actualize_virtual_card((mut card, level): VirtualCard, cards: &[Card]) -> Card {
     let mut trigger = false;
     let cards_len = cards.len() as u8;
     if card > cards_len {
         trigger = true;
         card -= cards_len;
     }
-    let card = cards[card as usize].clone();
+    let mut card = cards[card as usize].clone();
     if trigger {
-        library::as_trigger(card)
-    } else {
-        card
+        card = library::as_trigger(card);
+    }
+    for _ in 0..level {
+        card = library::as_upgraded(card);
     }
+    card
 }
-fn virtualize_card(card: &Card, cards: &[Card]) -> u8 {
+fn virtualize_card(card: &Card, cards: &[Card]) -> VirtualCard {
     let mut index = cards.iter().position(|f| f == card).unwrap() as u8;
     let cards_len = cards.len() as u8;
     if card.is_trigger {
         index += cards_len;
     }
-    index
+    (index, card.level)
 }
 impl SaveData {
     pub fn create(sludge: &Sludge) -> Self {
         let all_cards = get_cards();
-        let mut shop_items = sludge.ui_manager.shop.as_ref().unwrap().cards.clone();
+        let shop = sludge.ui_manager.shop.as_ref().unwrap();
+        let mut shop_items = shop.cards.clone();
 
-        let shop_items: [Option<(u8, u16)>; _] = std::array::from_fn(|index| {
-            shop_items[index / DEFAULT_SHOP_SLOTS_HORIZONTAL][index % DEFAULT_SHOP_SLOTS_HORIZONTAL]
+        let shop_items: [Option<(VirtualCard, u16, bool)>; _] = std::array::from_fn(|index| {
+            let (x, y) = (
+                index % DEFAULT_SHOP_SLOTS_HORIZONTAL,
+                index / DEFAULT_SHOP_SLOTS_HORIZONTAL,
+            );
+            shop_items[y][x]
                 .take()
-                .map(|(card, price)| (virtualize_card(&card, &all_cards), price))
+                .map(|(card, price)| (virtualize_card(&card, &all_cards), price, shop.locked[y][x]))
         });
         let all_towers = get_towers([(0, 0); 4]);
         let mut towers = std::array::from_fn(|_| None);
@@ -132,16 +143,22 @@ impl SaveData {
         new.ui_manager.gold = self.gold;
         new.round_manager.round = self.round_index as usize;
         let mut cards = Vec::new();
+        let mut locked = Vec::new();
         for (index, item) in self.shop_items.into_iter().enumerate() {
             let y = index / DEFAULT_SHOP_SLOTS_HORIZONTAL;
             if y >= cards.len() {
                 cards.push(Vec::new());
+                locked.push(Vec::new());
             }
+            locked.last_mut().unwrap().push(item.is_some_and(|f| f.2));
             let item =
-                item.map(|(index, price)| (actualize_virtual_card(index, &all_cards), price));
+                item.map(|(index, price, _)| (actualize_virtual_card(index, &all_cards), price));
             cards.last_mut().unwrap().push(item);
         }
-        let shop = ui::Shop { cards, open: false };
+        // the spawn shop is still open before the first round, and has no round
+        let round = (self.round_index as usize).checked_sub(1);
+        let mut shop = ui::Shop::new(cards, false, round);
+        shop.locked = locked;
         let mut towers = Vec::new();
         let all_towers = get_towers([(0, 0); 4]);
         for (index, tower_data) in self.towers.iter().enumerate() {
@@ -0,0 +1,91 @@
+//! Collision helpers. Kept free of other game modules, such that the benchmark can include this file directly.
+
+use macroquad::math::Vec2;
+
+/// Uniform grid used as a broad phase for collision checks.
+/// Stores indices of objects in the cell their position is in.
+pub struct SpatialGrid {
+    cell_size: f32,
+    width: usize,
+    height: usize,
+    cells: Vec<Vec<usize>>,
+}
+impl SpatialGrid {
+    pub fn new(width: f32, height: f32, cell_size: f32) -> Self {
+        let width = (width / cell_size).ceil() as usize;
+        let height = (height / cell_size).ceil() as usize;
+        Self {
+            cell_size,
+            width,
+            height,
+            cells: vec![Vec::new(); width * height],
+        }
+    }
+    pub fn clear(&mut self) {
+        for cell in self.cells.iter_mut() {
+            cell.clear();
+        }
+    }
+    /// Gets cell coordinates of position. Positions outside the grid are put in the nearest edge cell.
+    fn get_cell(&self, pos: Vec2) -> (usize, usize) {
+        let x = (pos.x / self.cell_size).max(0.0) as usize;
+        let y = (pos.y / self.cell_size).max(0.0) as usize;
+        (x.min(self.width - 1), y.min(self.height - 1))
+    }
+    pub fn insert(&mut self, index: usize, pos: Vec2) {
+        let (x, y) = self.get_cell(pos);
+        self.cells[y * self.width + x].push(index);
+    }
+    /// Pushes the indices of all objects in cells overlapping the rectangle from `min` to `max` onto `out`
+    pub fn query(&self, min: Vec2, max: Vec2, out: &mut Vec<usize>) {
+        let (min_x, min_y) = self.get_cell(min);
+        let (max_x, max_y) = self.get_cell(max);
+        for y in min_y..=max_y {
+            for x in min_x..=max_x {
+                out.extend_from_slice(&self.cells[y * self.width + x]);
+            }
+        }
+    }
+    /// Finds the objects whose circle the segment from `from` to `to` enters, as how far along the segment
+    /// they were reached and their index, sorted by the former. `centre` gets an object's position,
+    /// or None if it should be ignored. Both buffers are cleared first, such that they can be reused between calls
+    pub fn query_segment(
+        &self,
+        from: Vec2,
+        to: Vec2,
+        radius: f32,
+        centre: impl Fn(usize) -> Option<Vec2>,
+        candidates: &mut Vec<usize>,
+        hits: &mut Vec<(f32, usize)>,
+    ) {
+        candidates.clear();
+        hits.clear();
+        self.query(from.min(to) - radius, from.max(to) + radius, candidates);
+        hits.extend(candidates.iter().filter_map(|index| {
+            let t = segment_circle_entry(from, to, centre(*index)?, radius)?;
+            Some((t, *index))
+        }));
+        hits.sort_by(|a, b| a.0.total_cmp(&b.0));
+    }
+}
+
+/// Finds how far along the segment (0.0 - 1.0) it first enters the circle, if it does
+pub fn segment_circle_entry(from: Vec2, to: Vec2, centre: Vec2, radius: f32) -> Option<f32> {
+    let offset = from - centre;
+    if offset.length_squared() < radius * radius {
+        return Some(0.0);
+    }
+    let delta = to - from;
+    let a = delta.length_squared();
+    if a == 0.0 {
+        return None;
+    }
+    let b = 2.0 * offset.dot(delta);
+    let c = offset.length_squared() - radius * radius;
+    let discriminant = b * b - 4.0 * a * c;
+    if discriminant < 0.0 {
+        return None;
+    }
+    let t = (-b - discriminant.sqrt()) / (2.0 * a);
+    (0.0..=1.0).contains(&t).then_some(t)
+}
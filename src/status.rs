@@ -0,0 +1,168 @@
+use crate::{cards::DamageType, consts::*};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum StatusKind {
+    Poison,
+    Stun,
+    Freeze,
+    Burning,
+    Knockback,
+    Haste,
+}
+
+#[derive(Clone, Copy)]
+/// How a status reacts to being applied to an enemy that already has it
+pub enum StackingRule {
+    /// Frames are added onto the remaining frames, up to a max
+    Add(u8),
+    /// Remaining frames are set to the new frames, if they're longer
+    Refresh,
+    /// Never wears off once applied, only removed by effects like cleanse
+    Permanent,
+}
+
+#[derive(Clone, Copy)]
+/// What a status does to an enemy each frame
+pub enum StatusTick {
+    None,
+    /// Deals damage of type each frame
+    Damage(DamageType, f32),
+}
+
+/// Struct that holds information about a type of status effect
+pub struct StatusEffectType {
+    pub kind: StatusKind,
+    pub stacking: StackingRule,
+    pub tick: StatusTick,
+    /// Factor enemy speed is multiplied by while status is active
+    pub speed_factor: f32,
+    /// Used instead of speed_factor for enemies larger than a single tile
+    pub large_speed_factor: f32,
+    /// After a status is applied, the enemy can't get it again until the status has
+    /// worn off, and then this many frames have passed.
+    pub immunity_frames: u8,
+    /// Enemies resistant to this damage type can't get the status at all
+    pub resisted_by: Option<DamageType>,
+    /// Should status be passed on to children on death
+    pub inherited: bool,
+}
+
+#[derive(Clone, Copy)]
+/// A status effect active on an enemy
+pub struct StatusEffect {
+    pub ty: &'static StatusEffectType,
+    /// Frames left of status. When 0, status is only kept around to give immunity
+    pub frames: u8,
+    pub immunity_frames: u8,
+}
+
+pub static POISON: StatusEffectType = StatusEffectType {
+    kind: StatusKind::Poison,
+    stacking: StackingRule::Permanent,
+    tick: StatusTick::Damage(DamageType::Acid, POISON_DAMAGE),
+    speed_factor: 1.0,
+    large_speed_factor: 1.0,
+    immunity_frames: 0,
+    resisted_by: None,
+    inherited: false,
+};
+
+/// A rapid-firing tower with stun could stop every enemy from moving past it,
+/// which is why stun has immunity frames.
+pub static STUN: StatusEffectType = StatusEffectType {
+    kind: StatusKind::Stun,
+    stacking: StackingRule::Add(35),
+    tick: StatusTick::None,
+    speed_factor: 0.0,
+    // make larger enemies not stun entirely
+    large_speed_factor: 0.4,
+    immunity_frames: STUN_IMMUNITY_FRAMES,
+    resisted_by: None,
+    inherited: false,
+};
+
+pub static FREEZE: StatusEffectType = StatusEffectType {
+    kind: StatusKind::Freeze,
+    stacking: StackingRule::Refresh,
+    tick: StatusTick::None,
+    speed_factor: 0.55,
+    large_speed_factor: 0.55,
+    immunity_frames: 0,
+    resisted_by: Some(DamageType::Cold),
+    inherited: true,
+};
+
+pub static BURNING: StatusEffectType = StatusEffectType {
+    kind: StatusKind::Burning,
+    stacking: StackingRule::Refresh,
+    tick: StatusTick::Damage(DamageType::Burn, BURN_DAMAGE),
+    speed_factor: 1.0,
+    large_speed_factor: 1.0,
+    immunity_frames: 0,
+    resisted_by: Some(DamageType::Burn),
+    inherited: false,
+};
+
+/// Knockback happens instantly on hit, the status only exists to give immunity,
+/// so enemies can't be juggled in place by rapid-firing towers.
+pub static KNOCKBACK: StatusEffectType = StatusEffectType {
+    kind: StatusKind::Knockback,
+    stacking: StackingRule::Refresh,
+    tick: StatusTick::None,
+    speed_factor: 1.0,
+    large_speed_factor: 1.0,
+    immunity_frames: KNOCKBACK_IMMUNITY_FRAMES,
+    resisted_by: None,
+    inherited: false,
+};
+
+/// Given to enemies by a speed aura ability
+pub static HASTE: StatusEffectType = StatusEffectType {
+    kind: StatusKind::Haste,
+    stacking: StackingRule::Refresh,
+    tick: StatusTick::None,
+    speed_factor: 1.5,
+    large_speed_factor: 1.5,
+    immunity_frames: 0,
+    resisted_by: None,
+    inherited: false,
+};
+
+/// An interaction between a damage type and a status, evaluated when an enemy with the status is hit
+pub struct Reaction {
+    pub status: StatusKind,
+    pub damage_type: DamageType,
+    /// Factor the damage of the type is multiplied by
+    pub damage_factor: f32,
+    /// Does the reaction remove the status from the enemy
+    pub consumes_status: bool,
+    /// Does the reaction make trigger projectiles release their payload an extra time, once per cast
+    pub extra_payload: bool,
+}
+
+pub static REACTIONS: &[Reaction] = &[
+    // cold extinguishes burning enemies
+    Reaction {
+        status: StatusKind::Burning,
+        damage_type: DamageType::Cold,
+        damage_factor: 1.5,
+        consumes_status: true,
+        extra_payload: false,
+    },
+    // magic conducts through acid, making ex. lightning fork once per cast
+    Reaction {
+        status: StatusKind::Poison,
+        damage_type: DamageType::Magic,
+        damage_factor: 1.0,
+        consumes_status: false,
+        extra_payload: true,
+    },
+    // frozen enemies shatter
+    Reaction {
+        status: StatusKind::Freeze,
+        damage_type: DamageType::Pierce,
+        damage_factor: 2.0,
+        consumes_status: false,
+        extra_payload: false,
+    },
+];
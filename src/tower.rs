@@ -64,6 +64,32 @@ pub struct Tower {
     pub recharge_speed: f32,
     pub delay_counter: f32,
     pub direction: Vec2,
+    /// Seconds until the tower can shoot again, after being disabled by an enemy
+    pub disabled: f32,
+    /// Seconds left to show that an enemy scrambled the card index
+    pub scrambled: f32,
+}
+impl Tower {
+    /// Stops tower from shooting or recharging for some seconds
+    pub fn disable(&mut self, seconds: f32) {
+        self.disabled = self.disabled.max(seconds);
+    }
+    /// Moves card index to a random card, so the tower fires its deck out of order
+    pub fn scramble(&mut self) {
+        let cards = self.card_slots.iter().flatten().count();
+        if cards > 0 {
+            self.card_index = rand::gen_range(0, cards);
+        }
+        self.scrambled = SCRAMBLE_INDICATOR_TIME;
+    }
+    /// Does any card give detection, making the tower reveal camouflaged enemies near it
+    pub fn has_detection(&self) -> bool {
+        self.card_slots.iter().flatten().any(|card| match &card.ty {
+            CardType::Modifier(modifier_data) => modifier_data.detection,
+            CardType::Projectile(projectile, _) => projectile.modifier_data.detection,
+            _ => false,
+        })
+    }
 }
 impl PartialEq for Tower {
     fn eq(&self, other: &Self) -> bool {
@@ -71,12 +97,15 @@ impl PartialEq for Tower {
     }
 }
 
-fn draw_next(deck: &mut VecDeque<Card>) -> Vec<Card> {
+/// `add_trigger` is whether an "add trigger" modifier drawn before this call is still waiting for a projectile
+fn draw_next(deck: &mut VecDeque<Card>, divide_depth: u8, mut add_trigger: bool) -> Vec<Card> {
     let mut cards = Vec::new();
     let mut current_draw = 1;
+    // set by the "add trigger" modifier, makes the next projectile drawn act as a trigger
     while let Some(mut card) = deck.pop_front() {
         match &mut card.ty {
-            CardType::Modifier(_) => {
+            CardType::Modifier(modifier_data) => {
+                add_trigger |= modifier_data.add_trigger;
                 cards.push(card);
             }
             CardType::Multidraw(draw) => {
@@ -84,14 +113,66 @@ fn draw_next(deck: &mut VecDeque<Card>) -> Vec<Card> {
                 current_draw -= 1;
                 cards.push(card);
             }
-            CardType::Projectile(projectile, _) => {
+            CardType::Divide(amount) => {
+                current_draw -= 1;
+                // when too deep into nested divide cards, they stop copying
+                let amount = if divide_depth < MAX_DIVIDE_DEPTH {
+                    *amount
+                } else {
+                    1
+                };
+                cards.push(card);
+
+                // the divided projectile is the one a pending "add trigger" applies to
+                let mut drawn = draw_next(deck, divide_depth + 1, add_trigger);
+                add_trigger = false;
+                if amount > 1 {
+                    for card in drawn.iter_mut() {
+                        // payloads are weakened too, so ex. explosions aren't copied for free
+                        if let CardType::Projectile(projectile, _) = &mut card.ty {
+                            projectile.scale_damage(DIVIDE_DAMAGE_FACTOR);
+                        }
+                    }
+                }
+                // modifiers apply to every projectile in the cast anyways, so only projectiles are copied.
+                // the copies also shouldn't make the tower any slower
+                let copies: Vec<Card> = drawn
+                    .iter()
+                    .filter(|card| matches!(card.ty, CardType::Projectile(_, _)))
+                    .cloned()
+                    .map(|mut card| {
+                        if let CardType::Projectile(projectile, _) = &mut card.ty {
+                            projectile.modifier_data.shoot_delay = 0.0;
+                            projectile.modifier_data.recharge_speed = 0.0;
+                        }
+                        card
+                    })
+                    .collect();
+                cards.append(&mut drawn);
+                for _ in 1..amount {
+                    cards.extend(copies.iter().cloned());
+                }
+
+                if current_draw == 0 {
+                    break;
+                }
+            }
+            CardType::Projectile(projectile, can_trigger) => {
                 current_draw -= 1;
 
                 // if card is trigger, draw one more time and set that as this card's payload
                 if card.is_trigger {
-                    let payload = draw_next(deck);
+                    let payload = draw_next(deck, divide_depth, false);
                     projectile.payload = payload;
+                } else if add_trigger && *can_trigger {
+                    // projectiles that already have a payload (like the rocket) keep it,
+                    // and also release the newly drawn one.
+                    // like trigger cards, only projectiles allowed to be triggers take it,
+                    // on any other projectile the modifier is wasted
+                    let mut payload = draw_next(deck, divide_depth, false);
+                    projectile.payload.append(&mut payload);
                 }
+                add_trigger = false;
                 cards.push(card);
 
                 if current_draw == 0 {
@@ -139,6 +220,7 @@ pub fn fire_deck(
     for card in deck {
         if let CardType::Projectile(mut projectile, _) = card.ty {
             projectile.modifier_data.merge(&context.modifier_data);
+            projectile.modifier_data.apply_factors();
 
             let max_spread = projectile.modifier_data.spread.max(0.0);
             projectile.x = origin_x + SPRITE_SIZE / 2.0;
@@ -166,6 +248,7 @@ impl Tower {
         context.modifier_data.recharge_speed = self.recharge_speed;
         context.modifier_data.shoot_delay = self.shoot_delay;
         fire_deck(self.x, self.y, self.direction, drawn, &mut context);
+        context.modifier_data.apply_factors();
 
         let mut cooldown = context.modifier_data.shoot_delay;
         self.delay_counter = context.modifier_data.shoot_delay;
@@ -184,10 +267,92 @@ impl Tower {
             deck.push_back(popped);
         }
         let old_length = deck.len();
-        let drawn = draw_next(&mut deck);
+        let drawn = draw_next(&mut deck, 0, false);
         let new_length = deck.len();
         let amount_fired = old_length - new_length;
         self.card_index += amount_fired;
         (drawn, self.card_index >= old_length)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::library;
+
+    /// Sums damage of a projectile and all projectiles it releases
+    fn total_damage(projectile: &Projectile) -> f32 {
+        let children: f32 = projectile
+            .payload
+            .iter()
+            .chain(projectile.death_payload.iter())
+            .map(|child| match &child.ty {
+                CardType::Projectile(child, _) => total_damage(child),
+                _ => 0.0,
+            })
+            .sum();
+        projectile.modifier_data.damage.values().sum::<f32>() + children
+    }
+
+    #[test]
+    fn divide_weakens_payloads() {
+        for card in [library::fireball(), library::rocket(), library::lightning()] {
+            let CardType::Projectile(original, _) = &card.ty else {
+                panic!("{} isn't a projectile", card.name);
+            };
+            let expected = total_damage(original) * DIVIDE_DAMAGE_FACTOR;
+            let mut deck = VecDeque::from([library::divide_by_4(), card.clone()]);
+            let drawn = draw_next(&mut deck, 0, false);
+            let copies: Vec<&Projectile> = drawn
+                .iter()
+                .filter_map(|card| match &card.ty {
+                    CardType::Projectile(projectile, _) => Some(projectile),
+                    _ => None,
+                })
+                .collect();
+            assert_eq!(copies.len(), 4);
+            for copy in copies {
+                assert!(
+                    (total_damage(copy) - expected).abs() < 0.001,
+                    "{}",
+                    card.name
+                );
+            }
+        }
+    }
+
+    /// Payloads each drawn projectile releases, in draw order
+    fn payload_counts(drawn: &[Card]) -> Vec<usize> {
+        drawn
+            .iter()
+            .filter_map(|card| match &card.ty {
+                CardType::Projectile(projectile, _) => Some(projectile.payload.len()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn add_trigger_goes_through_divide() {
+        let mut deck = VecDeque::from([
+            library::add_trigger(),
+            library::divide_by_2(),
+            library::magicbolt(),
+            library::dart(),
+        ]);
+        let drawn = draw_next(&mut deck, 0, false);
+        assert_eq!(payload_counts(&drawn), vec![1, 1]);
+    }
+
+    #[test]
+    fn add_trigger_needs_trigger_projectile() {
+        let mut deck =
+            VecDeque::from([library::add_trigger(), library::fireball(), library::dart()]);
+        let drawn = draw_next(&mut deck, 0, false);
+        assert_eq!(
+            payload_counts(&drawn),
+            payload_counts(&[library::fireball()])
+        );
+        assert_eq!(deck.len(), 1);
+    }
+}
@@ -50,6 +50,11 @@ impl TextEngine {
                 index = 'z' as usize - 'a' as usize + 13;
             }
 
+            // hardcoded index of '+'
+            if char == '+' {
+                index = 'z' as usize - 'a' as usize + 14;
+            }
+
             // for characters in range a-z
             if index >= 'a' as usize {
                 index -= 'a' as usize
@@ -70,6 +75,70 @@ impl TextEngine {
 pub struct Shop {
     pub cards: Vec<Vec<Option<(Card, u16)>>>,
     pub open: bool,
+    /// Round the shop was opened for, used to calculate sell prices and reroll costs.
+    /// Is None in the lab and the spawn shop, which can't be sold to, rerolled or locked.
+    pub round: Option<usize>,
+    /// Locked slots keep their card and price through rerolls, and into the next round's shop
+    pub locked: Vec<Vec<bool>>,
+    /// How many times the shop has been rerolled this round
+    pub rerolls: u16,
+}
+impl Shop {
+    pub fn new(cards: Vec<Vec<Option<(Card, u16)>>>, open: bool, round: Option<usize>) -> Self {
+        let locked = vec![vec![false; cards[0].len()]; cards.len()];
+        Self {
+            cards,
+            open,
+            round,
+            locked,
+            rerolls: 0,
+        }
+    }
+    /// Gold cost of the next reroll. Is None if the shop can't be rerolled
+    fn get_reroll_cost(&self) -> Option<u16> {
+        let round = self.round?;
+        let cost = (REROLL_BASE_COST + REROLL_COST_INCREASE * self.rerolls as f32)
+            * (1.0 + round as f32 / 7.5);
+        Some(((cost + 2.5) / 5.0) as u16 * 5)
+    }
+    /// Replaces all unlocked slots with new random cards
+    fn reroll(&mut self, round: usize) {
+        let cards = get_cards();
+        for (y, row) in self.cards.iter_mut().enumerate() {
+            for (x, slot) in row.iter_mut().enumerate() {
+                if !self.locked[y][x] {
+                    *slot = Some(get_random_shop_item(round, &cards));
+                }
+            }
+        }
+    }
+}
+
+/// Picks a random card and price for the shop
+fn get_random_shop_item(round: usize, cards: &[Card]) -> (Card, u16) {
+    let card = get_random_shop_card(round, cards);
+    let base_price = rand::gen_range(120.0, 210.0);
+    let big_rand = rand::gen_range(-1, 4);
+    let price = calc_card_price(&card, round, base_price, big_rand as f32 * 10.0);
+    (card, price)
+}
+
+/// Calculates the shop price of a card from a base price, based on the card and round.
+/// `extra` is added before the round based price increase is applied.
+fn calc_card_price(card: &Card, round: usize, mut price: f32, extra: f32) -> u16 {
+    let price_modifier = 1.0 + round as f32 / 7.5;
+    let projectile_penalty = 1.2 + round as f32 / 40.0;
+    if let CardType::Projectile(_, _) = &card.ty {
+        price *= projectile_penalty;
+    }
+    if card.is_trigger {
+        price += 115.0 * projectile_penalty;
+    }
+    price += extra;
+    price *= price_modifier;
+
+    // round to nearest 5 and convert to u16
+    ((price + 2.5) / 5.0) as u16 * 5
 }
 
 pub struct UIManager<'a> {
@@ -78,6 +147,8 @@ pub struct UIManager<'a> {
     pub tower_open: bool,
     pub cursor_card: Option<Card>,
     pub shop: Option<Shop>,
+    /// The shop of the last round, kept while a round is in progress such that its locked slots carry over
+    pub closed_shop: Option<Shop>,
     pub gold: u16,
     pub text_engine: &'a TextEngine,
 }
@@ -90,6 +161,7 @@ impl<'a> UIManager<'a> {
             tower_open: false,
             cursor_card: None,
             shop: None,
+            closed_shop: None,
             gold: STARTING_GOLD,
             text_engine,
         }
@@ -106,16 +178,10 @@ impl<'a> UIManager<'a> {
             }
             shop_cards[y][index % slots_horizontal] = Some((card, 0));
         }
-        self.shop = Some(Shop {
-            cards: shop_cards,
-            open: was_open,
-        });
+        self.shop = Some(Shop::new(shop_cards, was_open, None));
     }
     pub fn open_spawn_shop(&mut self) {
-        self.shop = Some(Shop {
-            cards: vec![vec![None; 4]; 2],
-            open: true,
-        });
+        self.shop = Some(Shop::new(vec![vec![None; 4]; 2], true, None));
         let shop = self.shop.as_mut().unwrap();
         let mut cards = vec![
             (library::road_thorns(), 150),
@@ -137,36 +203,43 @@ impl<'a> UIManager<'a> {
         }
     }
     pub fn open_shop(&mut self, round: usize, width: usize, height: usize) {
-        let price_modifier = 1.0 + round as f32 / 7.5;
-        let projectile_penalty = 1.2 + round as f32 / 40.0;
-        let cards = get_cards();
-        let mut shop_cards: Vec<Vec<Option<(Card, u16)>>> = Vec::new();
-        for _ in 0..height {
-            let mut row = Vec::with_capacity(width);
-            for _ in 0..width {
-                let card = get_random_shop_card(round, &cards);
-                let mut price = rand::gen_range(120.0, 210.0);
-                if let CardType::Projectile(_, _) = &card.ty {
-                    price *= projectile_penalty;
-                }
-                if card.is_trigger {
-                    price += 115.0 * projectile_penalty;
+        let mut shop = Shop::new(vec![vec![None; width]; height], true, Some(round));
+        // carry over the locked slots of last round's shop
+        if let Some(old) = self.closed_shop.take() {
+            for (y, row) in old.cards.into_iter().enumerate().take(height) {
+                for (x, slot) in row.into_iter().enumerate().take(width) {
+                    if old.locked[y][x] {
+                        shop.cards[y][x] = slot;
+                        shop.locked[y][x] = true;
+                    }
                 }
-                let big_rand = rand::gen_range(-1, 4);
-                price += big_rand as f32 * 10.0;
-                price *= price_modifier;
-
-                // round to nearest 5 and convert to u16
-                let price = ((price + 2.5) / 5.0) as u16 * 5;
-
-                row.push(Some((card, price)));
             }
-            shop_cards.push(row);
         }
-        self.shop = Some(Shop {
-            cards: shop_cards,
-            open: true,
-        });
+        shop.reroll(round);
+        self.shop = Some(shop);
+    }
+    /// Closes the shop when a round starts, but remembers it such that locked slots are kept
+    pub fn close_shop(&mut self) {
+        self.closed_shop = self.shop.take();
+    }
+    /// Returns how much gold a card would sell for, if the shop is open and allows selling
+    fn get_sell_price(&self, card: &Card) -> Option<u16> {
+        let shop = self.shop.as_ref().filter(|shop| shop.open)?;
+        // shops without a round don't buy cards. the spawn shop's hand-set prices can be below sell prices
+        let round = shop.round?;
+        // fused cards are made out of several cards, and should sell for all of them
+        let amount = 2_u16.pow(card.level as u32);
+        let price = calc_card_price(card, round, SELL_BASE_PRICE, 0.0);
+        Some((price as f32 * SELL_REFUND_FACTOR) as u16 / 5 * 5 * amount)
+    }
+    /// Sells a card if possible, returning whether it was sold
+    fn try_sell(&mut self, card: &Card) -> bool {
+        if let Some(price) = self.get_sell_price(card) {
+            self.gold = self.gold.saturating_add(price);
+            true
+        } else {
+            false
+        }
     }
     fn draw_inventory(
         &mut self,
@@ -220,7 +293,16 @@ impl<'a> UIManager<'a> {
                         && !just_opened
                         && is_mouse_button_pressed(MouseButton::Left)
                     {
-                        std::mem::swap(&mut self.inventory[tile_y][tile_x], &mut self.cursor_card);
+                        // shift clicking a card sells it directly
+                        let selling =
+                            is_key_down(KeyCode::LeftShift) || is_key_down(KeyCode::RightShift);
+                        if let Some(card) = self.inventory[tile_y][tile_x].as_ref().cloned() {
+                            if selling && self.try_sell(&card) {
+                                self.inventory[tile_y][tile_x] = None;
+                                return;
+                            }
+                        }
+                        swap_or_fuse(&mut self.inventory[tile_y][tile_x], &mut self.cursor_card);
                     }
                 }
             }
@@ -253,8 +335,52 @@ impl<'a> UIManager<'a> {
         let shop_x = 0.0;
         let shop_y = SCREEN_HEIGHT - shop_height;
         draw_square(shop_x, shop_y, shop_width, shop_height);
-        self.text_engine
-            .draw_text(shop_x + 2.0, shop_y + 2.0, "card shop", 1);
+
+        // if holding a card that can be sold, show its price instead of the header,
+        // and sell it if it's dropped on the shop
+        let sell_price = self
+            .cursor_card
+            .as_ref()
+            .and_then(|card| self.get_sell_price(card));
+        let shop = self.shop.as_mut().unwrap();
+        if let Some(price) = sell_price {
+            self.text_engine
+                .draw_text(shop_x + 2.0, shop_y + 2.0, &format!("sell {price}"), 1);
+            if !just_opened
+                && is_mouse_button_pressed(MouseButton::Left)
+                && local_x < shop_width
+                && local_y > shop_y
+            {
+                self.cursor_card = None;
+                self.gold = self.gold.saturating_add(price);
+                return;
+            }
+        } else {
+            self.text_engine
+                .draw_text(shop_x + 2.0, shop_y + 2.0, "shop", 1);
+
+            // draw reroll button
+            if let (Some(cost), Some(round)) = (shop.get_reroll_cost(), shop.round) {
+                let text = format!("reroll {cost}");
+                let width = text.len() as f32 * 4.0 + 4.0;
+                if draw_button(
+                    self.text_engine,
+                    shop_x + shop_width - width - 1.0,
+                    shop_y + 1.0,
+                    width,
+                    7.0,
+                    local_x,
+                    local_y,
+                    &text,
+                ) && !just_opened
+                    && self.gold >= cost
+                {
+                    self.gold -= cost;
+                    shop.rerolls += 1;
+                    shop.reroll(round);
+                }
+            }
+        }
 
         let shop_y = SCREEN_HEIGHT - shop_height;
         for y in 0..shop.cards.len() {
@@ -262,6 +388,17 @@ impl<'a> UIManager<'a> {
                 let tile_y = SHOP_PADDING + shop_y + 2.0 + y as f32 * SHOP_CARD_HEIGHT;
                 let tile_x = 2.0 + x as f32 * SHOP_CARD_WIDTH;
 
+                let hovered = local_x == local_x.clamp(tile_x, tile_x + CARD_SIZE)
+                    && local_y == local_y.clamp(tile_y, tile_y + CARD_SIZE);
+                if shop.locked[y][x] {
+                    draw_rectangle(
+                        tile_x - 1.0,
+                        tile_y - 1.0,
+                        CARD_SIZE + 2.0,
+                        CARD_SIZE + 2.0,
+                        COLOR_YELLOW,
+                    );
+                }
                 if let Some((card, price)) = &shop.cards[y][x] {
                     self.text_engine
                         .draw_text(tile_x, tile_y - 5.0, &price.to_string(), 0);
@@ -269,16 +406,35 @@ impl<'a> UIManager<'a> {
                 } else {
                     draw_square(tile_x, tile_y, CARD_SIZE, CARD_SIZE);
                 }
+                // draw lock toggle in the bottom right corner of the card
+                let mut lock_hovered = false;
+                if shop.round.is_some() && shop.cards[y][x].is_some() {
+                    let lock_x = tile_x + CARD_SIZE - 4.0;
+                    let lock_y = tile_y + CARD_SIZE - 4.0;
+                    lock_hovered = local_x == local_x.clamp(lock_x, lock_x + 4.0)
+                        && local_y == local_y.clamp(lock_y, lock_y + 4.0);
+                    if lock_hovered && !just_opened && is_mouse_button_pressed(MouseButton::Left) {
+                        shop.locked[y][x] = !shop.locked[y][x];
+                    }
+                    let color = if shop.locked[y][x] || lock_hovered {
+                        COLOR_YELLOW
+                    } else {
+                        COLOR_BEIGE
+                    };
+                    draw_rectangle(lock_x, lock_y, 4.0, 4.0, COLOR_BROWN);
+                    draw_rectangle(lock_x + 1.0, lock_y + 1.0, 2.0, 2.0, color);
+                }
                 if !just_opened
                     && is_mouse_button_pressed(MouseButton::Left)
                     && self.cursor_card.is_none()
-                    && local_x == local_x.clamp(tile_x, tile_x + CARD_SIZE)
-                    && local_y == local_y.clamp(tile_y, tile_y + CARD_SIZE)
+                    && hovered
+                    && !lock_hovered
                     && shop.cards[y][x].is_some()
                 {
                     let price = (shop.cards[y][x].as_ref()).map(|f| f.1).unwrap();
                     if self.gold >= price {
                         self.gold -= price;
+                        shop.locked[y][x] = false;
                         let (card, _) = shop.cards[y][x].take().unwrap();
                         self.cursor_card = Some(card);
                         self.inventory_open = true;
@@ -321,6 +477,9 @@ impl<'a> UIManager<'a> {
         if card.is_trigger {
             name += " trigger"
         }
+        if card.level > 0 {
+            name += &format!(" lv{}", card.level + 1);
+        }
         self.text_engine
             .draw_text(local_x + 4.0 + CARD_SIZE, local_y + 3.0, &name, 1);
         self.text_engine
@@ -557,7 +716,7 @@ impl<'a> UIManager<'a> {
                     self.draw_card_info(local_x, local_y, card, card_sheet);
                 }
                 if is_mouse_button_pressed(MouseButton::Left) {
-                    std::mem::swap(
+                    swap_or_fuse(
                         &mut tower_card_slots.unwrap()[tile_x],
                         &mut self.cursor_card,
                     );
@@ -567,6 +726,18 @@ impl<'a> UIManager<'a> {
     }
 }
 
+/// Places the cursor card in a slot by swapping them, or if they're identical, fuses them into an upgraded card.
+fn swap_or_fuse(slot: &mut Option<Card>, cursor_card: &mut Option<Card>) {
+    if let (Some(card), Some(held)) = (slot.as_ref(), cursor_card.as_ref()) {
+        if card.can_fuse(held) {
+            *cursor_card = None;
+            *slot = slot.take().map(library::as_upgraded);
+            return;
+        }
+    }
+    std::mem::swap(slot, cursor_card);
+}
+
 pub fn draw_square(x: f32, y: f32, w: f32, h: f32) {
     draw_rectangle(x, y, w, h, COLOR_BROWN);
     draw_rectangle(x + 1.0, y + 1.0, w - 2.0, h - 2.0, COLOR_BEIGE);